@@ -0,0 +1,252 @@
+#![feature(test)]
+extern crate test;
+
+use hash::crc::{Crc32HashFunction, Crc32cHashFunction};
+use hash::fnv::FnvHashFunction;
+use hash::fx::FxHashFunction;
+use hash::murmur3::{Murmur3x128HashFunction, Murmur3x32HashFunction};
+use hash::sha2::Sha256HashFunction;
+use hash::sip::{SipHashFunction, Sip13HashFunction};
+use hash::traits::{Hash, HashContext, HashFunction, ResettableContext};
+use hash::tree::TreeHash;
+use hash::xxh::Xxh3HashFunction;
+
+use test::{Bencher,black_box};
+
+#[allow(deprecated)]
+struct OrigSip {
+    k0: u64,
+    k1: u64,
+}
+
+#[allow(deprecated)]
+impl HashFunction for OrigSip {
+    type Context = std::hash::SipHasher;
+
+    #[inline(always)]
+    fn init(&self) -> Self::Context {
+        std::hash::SipHasher::new_with_keys(self.k0, self.k1)
+    }
+}
+
+const ITERS : u64 = 1000;
+
+macro_rules! gen_hash_bench {
+    ($name:ident, $v:expr, $b:expr) => {
+        #[bench]
+        #[allow(deprecated)]
+        fn $name(b: &mut Bencher) {
+            b.iter(|| for _ in 0..ITERS {
+                let arg = $v;
+                let arg = black_box(arg);
+                let mut hasher = std::hash::SipHasher::new_with_keys(7, 39);
+                std::hash::Hash::hash(&arg, &mut hasher);
+                black_box(std::hash::Hasher::finish(&hasher));
+            });
+            b.bytes = $b * ITERS;
+        }
+    }
+}
+
+macro_rules! gen_digest_bench {
+    ($name:ident, $v:expr, $b:expr, $hash:expr) => {
+        #[bench]
+        fn $name(b: &mut Bencher) {
+            b.iter(|| for _ in 0..ITERS {
+                let arg = $v;
+                let arg = black_box(arg);
+                black_box(arg.digest(&$hash));
+            });
+            b.bytes = $b * ITERS;
+        }
+    }
+}
+
+macro_rules! gen_oneshot_bench {
+    ($name:ident, $v:expr, $b:expr, $hash:expr) => {
+        #[bench]
+        fn $name(b: &mut Bencher) {
+            let f = $hash;
+            b.iter(|| for _ in 0..ITERS {
+                let arg = $v;
+                let arg = black_box(arg);
+                black_box(f.digest(arg));
+            });
+            b.bytes = $b * ITERS;
+        }
+    }
+}
+
+// One-shot digest of a large buffer, and streaming it after a 1-byte
+// update so that the bulk of the input goes through the unaligned path
+macro_rules! gen_bulk_bench {
+    ($name:ident, $stream:ident, $len:expr, $hash:expr) => {
+        #[bench]
+        fn $name(b: &mut Bencher) {
+            let f = $hash;
+            let bytes = vec![42u8; $len];
+            b.iter(|| black_box(f.digest(black_box(&bytes[..]))));
+            b.bytes = $len as u64;
+        }
+
+        #[bench]
+        fn $stream(b: &mut Bencher) {
+            let f = $hash;
+            let bytes = vec![42u8; $len];
+            b.iter(|| {
+                let bytes = black_box(&bytes[..]);
+                let mut ctx = f.init();
+                ctx.update(&bytes[..1]);
+                ctx.update(&bytes[1..]);
+                black_box(ctx.finish())
+            });
+            b.bytes = $len as u64;
+        }
+    }
+}
+
+macro_rules! gen_stream_bench {
+    ($name:ident, $v:expr, $b:expr, $hash:expr) => {
+        #[bench]
+        fn $name(b: &mut Bencher) {
+            let f = $hash;
+            b.iter(|| for _ in 0..ITERS {
+                let arg = $v;
+                let arg = black_box(arg);
+                let mut ctx = f.init();
+                ctx.update(arg);
+                black_box(ctx.finish());
+            });
+            b.bytes = $b * ITERS;
+        }
+    }
+}
+
+macro_rules! gen_reset_bench {
+    ($name:ident, $v:expr, $b:expr, $hash:expr) => {
+        #[bench]
+        fn $name(b: &mut Bencher) {
+            let mut ctx = $hash.init();
+            b.iter(|| for _ in 0..ITERS {
+                let arg = $v;
+                let arg = black_box(arg);
+                arg.hash(&mut ctx);
+                black_box(ctx.finish_reset());
+            });
+            b.bytes = $b * ITERS;
+        }
+    }
+}
+
+macro_rules! gen_pool_bench {
+    ($name:ident, $v:expr, $b:expr, $hash:expr) => {
+        #[bench]
+        fn $name(b: &mut Bencher) {
+            let mut pool = $hash.hasher_pool();
+            b.iter(|| for _ in 0..ITERS {
+                let arg = $v;
+                let arg = black_box(arg);
+                black_box(pool.digest_value(&arg));
+            });
+            b.bytes = $b * ITERS;
+        }
+    }
+}
+
+gen_hash_bench!{bench_hash_u8,  42u8, 1}
+gen_hash_bench!{bench_hash_u8_,  43u8, 1}
+gen_hash_bench!{bench_hash_u32, 42u32, 4}
+gen_hash_bench!{bench_hash_u64, 42u64, 8}
+gen_hash_bench!{bench_hash_u81, &[42u8; 1], 1}
+gen_hash_bench!{bench_hash_u82, &[42u8; 2], 2}
+gen_hash_bench!{bench_hash_u87, &[42u8; 7], 7}
+
+gen_digest_bench!{bench_digest_my_u8, 42u8, 1, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_digest_bench!{bench_digest_my_u8_, 43u8, 1, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_digest_bench!{bench_digest_my_int_u32, 42u32, 4, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_digest_bench!{bench_digest_my_int_u64, 42u64, 8, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_digest_bench!{bench_digest_my_int_tuple, (1u8, 2u32, 3u64), 13, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_digest_bench!{bench_digest_my_u81, &[42u8; 1], 1, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_digest_bench!{bench_digest_my_u82, &[42u8; 2], 2, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_digest_bench!{bench_digest_my_u83, &[42u8; 3], 3, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_digest_bench!{bench_digest_my_u84, &[42u8; 4], 4, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_digest_bench!{bench_digest_my_u85, &[42u8; 5], 5, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_digest_bench!{bench_digest_my_u86, &[42u8; 6], 6, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_digest_bench!{bench_digest_my_u87, &[42u8; 7], 7, black_box(SipHashFunction::new_with_keys(7, 39)) }
+
+gen_digest_bench!{bench_digest_my_slice_u81, &[42u8; 1][..], 1, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_digest_bench!{bench_digest_my_slice_u87, &[42u8; 7][..], 7, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_digest_bench!{bench_digest_my_slice_u864, &[42u8; 64][..], 64, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_digest_bench!{bench_digest_my_str7, "abcdefg", 7, black_box(SipHashFunction::new_with_keys(7, 39)) }
+
+gen_oneshot_bench!{bench_oneshot_my_u81, &[42u8; 1][..], 1, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_oneshot_bench!{bench_oneshot_my_u87, &[42u8; 7][..], 7, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_oneshot_bench!{bench_oneshot_my_u88, &[42u8; 8][..], 8, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_oneshot_bench!{bench_oneshot_my_u816, &[42u8; 16][..], 16, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_oneshot_bench!{bench_oneshot_my_u832, &[42u8; 32][..], 32, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_oneshot_bench!{bench_oneshot_my_u864, &[42u8; 64][..], 64, black_box(SipHashFunction::new_with_keys(7, 39)) }
+
+gen_stream_bench!{bench_stream_my_u81, &[42u8; 1][..], 1, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_stream_bench!{bench_stream_my_u87, &[42u8; 7][..], 7, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_stream_bench!{bench_stream_my_u88, &[42u8; 8][..], 8, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_stream_bench!{bench_stream_my_u816, &[42u8; 16][..], 16, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_stream_bench!{bench_stream_my_u832, &[42u8; 32][..], 32, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_stream_bench!{bench_stream_my_u864, &[42u8; 64][..], 64, black_box(SipHashFunction::new_with_keys(7, 39)) }
+
+gen_reset_bench!{bench_reset_my_u8, 42u8, 1, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_reset_bench!{bench_reset_my_u81, &[42u8; 1], 1, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_reset_bench!{bench_reset_my_u87, &[42u8; 7], 7, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_pool_bench!{bench_pool_my_u8, 42u8, 1, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_pool_bench!{bench_pool_xxh3_u87, &[42u8; 7], 7, black_box(Xxh3HashFunction::new()) }
+gen_digest_bench!{bench_digest_xxh3_u87, &[42u8; 7], 7, black_box(Xxh3HashFunction::new()) }
+
+gen_bulk_bench!{bench_bulk_my_1k, bench_bulk_stream_my_1k, 1 << 10, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_bulk_bench!{bench_bulk_my_64k, bench_bulk_stream_my_64k, 64 << 10, black_box(SipHashFunction::new_with_keys(7, 39)) }
+gen_bulk_bench!{bench_bulk_my_1m, bench_bulk_stream_my_1m, 1 << 20, black_box(SipHashFunction::new_with_keys(7, 39)) }
+
+gen_bulk_bench!{bench_bulk_tree_my_1m, bench_bulk_stream_tree_my_1m, 1 << 20, black_box(TreeHash::new(SipHashFunction::new_with_keys(7, 39), 64 << 10)) }
+
+gen_bulk_bench!{bench_bulk_crc32_64k, bench_bulk_stream_crc32_64k, 64 << 10, black_box(Crc32HashFunction::new()) }
+gen_bulk_bench!{bench_bulk_crc32c_64k, bench_bulk_stream_crc32c_64k, 64 << 10, black_box(Crc32cHashFunction::new()) }
+
+gen_bulk_bench!{bench_bulk_murmur3_32_64k, bench_bulk_stream_murmur3_32_64k, 64 << 10, black_box(Murmur3x32HashFunction::new()) }
+gen_bulk_bench!{bench_bulk_murmur3_128_64k, bench_bulk_stream_murmur3_128_64k, 64 << 10, black_box(Murmur3x128HashFunction::new()) }
+gen_bulk_bench!{bench_bulk_sha256_64k, bench_bulk_stream_sha256_64k, 64 << 10, black_box(Sha256HashFunction::new()) }
+
+gen_digest_bench!{bench_digest_my13_u8, 42u8, 1, black_box(Sip13HashFunction::new_with_keys(7, 39)) }
+gen_digest_bench!{bench_digest_my13_u81, &[42u8; 1], 1, black_box(Sip13HashFunction::new_with_keys(7, 39)) }
+gen_digest_bench!{bench_digest_my13_u87, &[42u8; 7], 7, black_box(Sip13HashFunction::new_with_keys(7, 39)) }
+
+#[bench]
+fn bench_digest_my_16m(b: &mut Bencher) {
+    let f = black_box(SipHashFunction::new_with_keys(7, 39));
+    let bytes = vec![42u8; 16 << 20];
+    b.iter(|| black_box(f.digest(&bytes[..])));
+    b.bytes = bytes.len() as u64;
+}
+
+#[bench]
+fn bench_digest_prefetch_my_16m(b: &mut Bencher) {
+    let f = black_box(SipHashFunction::new_with_keys(7, 39));
+    let bytes = vec![42u8; 16 << 20];
+    b.iter(|| black_box(f.digest_prefetch(&bytes)));
+    b.bytes = bytes.len() as u64;
+}
+
+gen_digest_bench!{bench_digest_fnv_u8, 42u8, 1, black_box(FnvHashFunction::new()) }
+gen_digest_bench!{bench_digest_fnv_u81, &[42u8; 1], 1, black_box(FnvHashFunction::new()) }
+gen_digest_bench!{bench_digest_fnv_u87, &[42u8; 7], 7, black_box(FnvHashFunction::new()) }
+gen_digest_bench!{bench_digest_fnv_u864, &[42u8; 64], 64, black_box(FnvHashFunction::new()) }
+
+gen_digest_bench!{bench_digest_fx_u8, 42u8, 1, black_box(FxHashFunction::new()) }
+gen_digest_bench!{bench_digest_fx_u81, &[42u8; 1], 1, black_box(FxHashFunction::new()) }
+gen_digest_bench!{bench_digest_fx_u87, &[42u8; 7], 7, black_box(FxHashFunction::new()) }
+gen_digest_bench!{bench_digest_fx_u864, &[42u8; 64], 64, black_box(FxHashFunction::new()) }
+
+gen_digest_bench!{bench_digest_my_u864, &[42u8; 64], 64, black_box(SipHashFunction::new_with_keys(7, 39)) }
+
+gen_digest_bench!{bench_digest_orig_u8, 42u8, 1, black_box(OrigSip{k0: 7, k1: 39}) }
+gen_digest_bench!{bench_digest_orig_u8_, 43u8, 1, black_box(OrigSip{k0: 7, k1: 39}) }
+gen_digest_bench!{bench_digest_orig_u81, &[42u8; 1], 1, black_box(OrigSip{k0: 7, k1: 39}) }
+gen_digest_bench!{bench_digest_orig_u82, &[42u8; 2], 2, black_box(OrigSip{k0: 7, k1: 39}) }
+gen_digest_bench!{bench_digest_orig_u87, &[42u8; 7], 7, black_box(OrigSip{k0: 7, k1: 39}) }
@@ -0,0 +1,101 @@
+//! `#[derive(Hash)]` for the `hash::traits::Hash` trait.
+//!
+//! Fields are hashed in declaration order. Enums first hash the index of
+//! the variant as a single byte, followed by the fields of the variant.
+//! This is not the `HashContext::update_variant()` framing, which would
+//! need the length of the fields before hashing them.
+//! Each derived value is a level of nesting for contexts that bound the
+//! recursion depth, see `HashContext::enter_nested()`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields, GenericParam, Index};
+
+#[proc_macro_derive(Hash)]
+pub fn derive_hash(input: TokenStream) -> TokenStream {
+    let mut input = parse_macro_input!(input as DeriveInput);
+
+    for param in &mut input.generics.params {
+        if let GenericParam::Type(ty) = param {
+            ty.bounds.push(parse_quote!(::hash::traits::Hash));
+        }
+    }
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let fields = data.fields.iter().enumerate().map(|(i, field)| {
+                match &field.ident {
+                    Some(ident) => quote! { ::hash::traits::Hash::hash(&self.#ident, ctx); },
+                    None => {
+                        let index = Index::from(i);
+                        quote! { ::hash::traits::Hash::hash(&self.#index, ctx); }
+                    }
+                }
+            });
+            quote! { #(#fields)* }
+        }
+        Data::Enum(data) => {
+            if data.variants.len() > 256 {
+                return syn::Error::new_spanned(&input.ident, "Hash can only be derived for enums with at most 256 variants")
+                    .to_compile_error()
+                    .into();
+            }
+
+            let arms = data.variants.iter().enumerate().map(|(i, variant)| {
+                let ident = &variant.ident;
+                let tag = i as u8;
+                let (pattern, fields) = bind_fields(&variant.fields);
+                quote! {
+                    Self::#ident #pattern => {
+                        ::hash::traits::Hash::hash(&#tag, ctx);
+                        #fields
+                    }
+                }
+            });
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => {
+            return syn::Error::new_spanned(&input.ident, "Hash cannot be derived for unions")
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let expanded = quote! {
+        impl #impl_generics ::hash::traits::Hash for #name #ty_generics #where_clause {
+            #[inline]
+            fn hash<__H: ::hash::traits::HashContext>(&self, ctx: &mut __H) {
+                if ::hash::traits::HashContext::enter_nested(ctx) {
+                    #body
+                }
+                ::hash::traits::HashContext::leave_nested(ctx);
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+/// Build the pattern binding all the fields of an enum variant, together
+/// with the statements hashing them.
+fn bind_fields(fields: &Fields) -> (TokenStream2, TokenStream2) {
+    let names: Vec<_> = (0..fields.len()).map(|i| format_ident!("__field{}", i)).collect();
+    let pattern = match fields {
+        Fields::Named(named) => {
+            let idents = named.named.iter().map(|field| &field.ident);
+            quote! { { #(#idents: #names),* } }
+        }
+        Fields::Unnamed(_) => quote! { ( #(#names),* ) },
+        Fields::Unit => quote! {},
+    };
+    let hashes = quote! { #(::hash::traits::Hash::hash(#names, ctx);)* };
+    (pattern, hashes)
+}
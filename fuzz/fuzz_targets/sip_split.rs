@@ -0,0 +1,49 @@
+#![no_main]
+
+use hash::sip::SipHashFunction;
+use hash::traits::{HashContext, HashFunction};
+use libfuzzer_sys::fuzz_target;
+
+// Input layout: 16 key bytes, one byte giving the number of split
+// lengths, that many split lengths (one byte each), then the message.
+fuzz_target!(|input: &[u8]| {
+    if input.len() < 17 {
+        return;
+    }
+    let (key, rest) = input.split_at(16);
+    let mut k = [0u8; 8];
+    k.copy_from_slice(&key[..8]);
+    let k0 = u64::from_le_bytes(k);
+    k.copy_from_slice(&key[8..]);
+    let k1 = u64::from_le_bytes(k);
+
+    let nsplits = std::cmp::min(rest[0] as usize, rest.len() - 1);
+    let (splits, data) = rest[1..].split_at(nsplits);
+    // all-empty splits would never make progress
+    let splits = if splits.iter().all(|&n| n == 0) { &[][..] } else { splits };
+
+    let f = SipHashFunction::new_with_keys(k0, k1);
+    let mut ctx = f.init();
+    let mut data_left = data;
+    let mut splits = splits.iter().cycle();
+    while !data_left.is_empty() {
+        let n = match splits.next() {
+            Some(&n) => std::cmp::min(n as usize, data_left.len()),
+            None => data_left.len(),
+        };
+        ctx.update(&data_left[..n]);
+        data_left = &data_left[n..];
+    }
+    let streamed = ctx.finish();
+
+    assert_eq!(streamed, f.digest(data));
+
+    #[allow(deprecated)]
+    let reference = {
+        use std::hash::Hasher;
+        let mut hasher = std::hash::SipHasher::new_with_keys(k0, k1);
+        hasher.write(data);
+        hasher.finish()
+    };
+    assert_eq!(streamed, reference);
+});
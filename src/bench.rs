@@ -0,0 +1,106 @@
+use std::hint::black_box;
+use std::time::Instant;
+
+use crate::crc::{Crc32HashFunction, Crc32cHashFunction};
+use crate::fnv::FnvHashFunction;
+use crate::fx::FxHashFunction;
+use crate::murmur3::{Murmur3x128HashFunction, Murmur3x32HashFunction};
+use crate::sha2::Sha256HashFunction;
+use crate::sip::{SipHashFunction, Sip13HashFunction};
+use crate::traits::HashFunction;
+use crate::xxh::{Xxh3HashFunction, Xxh64HashFunction};
+
+/// One-shot digest of an algorithm, truncated to 64 bits.
+pub type DigestFn = fn(&[u8]) -> u64;
+
+/// The algorithms measured by `compare_throughput`.
+pub const ALGORITHMS: &[(&str, DigestFn)] = &[
+    ("siphash-2-4", siphash),
+    ("siphash-1-3", siphash13),
+    ("std-siphash-2-4", std_siphash),
+    ("fnv-1a", fnv),
+    ("fxhash", fx),
+    ("xxh64", xxh64),
+    ("xxh3-64", xxh3),
+    ("crc32", crc32),
+    ("crc32c", crc32c),
+    ("sha-256", sha256),
+    ("murmur3-x86-32", murmur3_32),
+    ("murmur3-x64-128", murmur3_128),
+];
+
+fn siphash(bytes: &[u8]) -> u64 {
+    SipHashFunction::new_with_keys(7, 39).digest(bytes)
+}
+
+fn siphash13(bytes: &[u8]) -> u64 {
+    Sip13HashFunction::new_with_keys(7, 39).digest(bytes)
+}
+
+#[allow(deprecated)]
+fn std_siphash(bytes: &[u8]) -> u64 {
+    use std::hash::Hasher;
+
+    let mut hasher = std::hash::SipHasher::new_with_keys(7, 39);
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+fn fnv(bytes: &[u8]) -> u64 {
+    FnvHashFunction::new().digest(bytes)
+}
+
+fn fx(bytes: &[u8]) -> u64 {
+    FxHashFunction::new().digest(bytes)
+}
+
+fn xxh64(bytes: &[u8]) -> u64 {
+    Xxh64HashFunction::new().digest(bytes)
+}
+
+fn xxh3(bytes: &[u8]) -> u64 {
+    Xxh3HashFunction::new().digest(bytes)
+}
+
+fn crc32(bytes: &[u8]) -> u64 {
+    u64::from(Crc32HashFunction::new().digest(bytes))
+}
+
+fn crc32c(bytes: &[u8]) -> u64 {
+    u64::from(Crc32cHashFunction::new().digest(bytes))
+}
+
+fn murmur3_32(bytes: &[u8]) -> u64 {
+    u64::from(Murmur3x32HashFunction::new().digest(bytes))
+}
+
+fn murmur3_128(bytes: &[u8]) -> u64 {
+    Murmur3x128HashFunction::new().digest(bytes) as u64
+}
+
+fn sha256(bytes: &[u8]) -> u64 {
+    let digest = Sha256HashFunction::new().digest(bytes);
+    let mut word = [0u8; 8];
+    word.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(word)
+}
+
+/// Runtime throughput comparison of every algorithm in `ALGORITHMS`.
+///
+/// Hashes `input` `iters` times with each algorithm and returns its
+/// name along with the measured throughput in MB/s, so that users can
+/// profile on their own hardware and data. Panics if `input` is empty or
+/// `iters` is 0, as no throughput can be measured.
+pub fn compare_throughput(input: &[u8], iters: u64) -> Vec<(&'static str, f64)> {
+    assert!(!input.is_empty(), "input must not be empty");
+    assert!(iters > 0, "iters must be positive");
+    ALGORITHMS.iter().map(|&(name, digest)| {
+        let start = Instant::now();
+        for _ in 0..iters {
+            black_box(digest(black_box(input)));
+        }
+        let secs = start.elapsed().as_secs_f64();
+        let megabytes = input.len() as f64 * iters as f64 / 1e6;
+        (name, megabytes / secs)
+    }).collect()
+}
@@ -0,0 +1,130 @@
+#[cfg(feature = "serde")]
+use core::convert::TryFrom;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::sip::SipHashFunction;
+use crate::traits::{Hash, HashContext, HashFunction};
+
+/// A Bloom filter keyed with SipHash.
+///
+/// Bit positions are derived by double hashing two domain-separated
+/// SipHash digests of the item. The SipHash keys are part of the filter
+/// state, so a filter restored through serde answers membership queries
+/// exactly like the one that was serialized.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawBloom"))]
+pub struct BloomFilter {
+    bits: Vec<u64>,
+    num_bits: u64,
+    num_hashes: u32,
+    k0: u64,
+    k1: u64,
+}
+
+/// The serialized form of a `BloomFilter`, checked before it is turned
+/// back into a filter.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawBloom {
+    bits: Vec<u64>,
+    num_bits: u64,
+    num_hashes: u32,
+    k0: u64,
+    k1: u64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawBloom> for BloomFilter {
+    type Error = &'static str;
+
+    fn try_from(raw: RawBloom) -> Result<BloomFilter, &'static str> {
+        if raw.num_bits == 0 {
+            return Err("a Bloom filter needs at least one bit");
+        }
+        if raw.num_hashes == 0 {
+            return Err("a Bloom filter needs at least one hash");
+        }
+        if raw.bits.len() as u64 != raw.num_bits.div_ceil(64) {
+            return Err("the bit array does not match the number of bits");
+        }
+        Ok(BloomFilter {
+            bits: raw.bits,
+            num_bits: raw.num_bits,
+            num_hashes: raw.num_hashes,
+            k0: raw.k0,
+            k1: raw.k1,
+        })
+    }
+}
+
+impl BloomFilter {
+    #[inline]
+    pub fn new(num_bits: u64, num_hashes: u32) -> BloomFilter {
+        BloomFilter::new_with_keys(num_bits, num_hashes, 0, 0)
+    }
+
+    /// Panics if either `num_bits` or `num_hashes` is 0.
+    pub fn new_with_keys(num_bits: u64, num_hashes: u32, k0: u64, k1: u64) -> BloomFilter {
+        assert!(num_bits > 0, "a Bloom filter needs at least one bit");
+        assert!(num_hashes > 0, "a Bloom filter needs at least one hash");
+        BloomFilter {
+            bits: vec![0; num_bits.div_ceil(64) as usize],
+            num_bits,
+            num_hashes,
+            k0,
+            k1,
+        }
+    }
+
+    #[inline]
+    pub fn num_bits(&self) -> u64 {
+        self.num_bits
+    }
+
+    #[inline]
+    pub fn num_hashes(&self) -> u32 {
+        self.num_hashes
+    }
+
+    pub fn insert<T: Hash + ?Sized>(&mut self, item: &T) {
+        let (h1, h2) = self.hashes(item);
+        for i in 0..self.num_hashes {
+            let bit = self.bit(h1, h2, i);
+            self.bits[(bit / 64) as usize] |= 1 << (bit % 64);
+        }
+    }
+
+    /// Returns `false` if `item` was never inserted; `true` may be a
+    /// false positive.
+    pub fn contains<T: Hash + ?Sized>(&self, item: &T) -> bool {
+        let (h1, h2) = self.hashes(item);
+        (0..self.num_hashes).all(|i| {
+            let bit = self.bit(h1, h2, i);
+            self.bits[(bit / 64) as usize] & (1 << (bit % 64)) != 0
+        })
+    }
+
+    pub fn clear(&mut self) {
+        for word in &mut self.bits {
+            *word = 0;
+        }
+    }
+
+    fn hashes<T: Hash + ?Sized>(&self, item: &T) -> (u64, u64) {
+        let f = SipHashFunction::new_with_keys(self.k0, self.k1);
+        let digest = |domain: u8| {
+            let mut ctx = f.init();
+            ctx.update(&[domain]);
+            item.hash(&mut ctx);
+            ctx.finish()
+        };
+        (digest(0), digest(1))
+    }
+
+    #[inline]
+    fn bit(&self, h1: u64, h2: u64, i: u32) -> u64 {
+        h1.wrapping_add(u64::from(i).wrapping_mul(h2)) % self.num_bits
+    }
+}
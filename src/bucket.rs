@@ -0,0 +1,151 @@
+use crate::sip::SipHashFunction;
+use crate::traits::{Hash, HashContext, HashFunction};
+use crate::util::fast_range;
+
+impl SipHashFunction {
+    /// Deterministically assign `key` to one of `num_buckets` buckets.
+    ///
+    /// Panics if `num_buckets` is 0.
+    #[inline]
+    pub fn bucket_bytes(&self, key: &[u8], num_buckets: u32) -> u32 {
+        assert!(num_buckets > 0, "num_buckets must be positive");
+        fast_range(self.digest(key), u64::from(num_buckets)) as u32
+    }
+
+    /// `replicas` distinct shards in `[0, num_shards)` for `key`, the
+    /// first one being its primary shard.
+    ///
+    /// Candidates are derived from domain-separated digests of the key
+    /// and duplicates are skipped. `replicas` is clamped to `num_shards`.
+    pub fn shards(&self, key: &[u8], num_shards: u32, replicas: u32) -> Vec<u32> {
+        let replicas = std::cmp::min(replicas, num_shards) as usize;
+        let mut shards = Vec::with_capacity(replicas);
+        let mut domain = 0;
+        while shards.len() < replicas {
+            let hash = self.digest_domain("hash::shards", domain, key);
+            let shard = fast_range(hash, u64::from(num_shards)) as u32;
+            if !shards.contains(&shard) {
+                shards.push(shard);
+            }
+            domain += 1;
+        }
+        shards
+    }
+
+    /// Stable small integer in `[0, modulus)` for an enum variant name.
+    ///
+    /// Unlike ordinal assignment, the id of a name does not change when
+    /// other names are added. Distinct names can collide, with
+    /// probability about `n^2 / (2 * modulus)` for `n` names, so callers
+    /// must check their set of names for collisions (and pick a larger
+    /// modulus or other keys if needed). Panics if `modulus` is 0.
+    #[inline]
+    pub fn stable_id(&self, name: &str, modulus: u32) -> u32 {
+        self.bucket_bytes(name.as_bytes(), modulus)
+    }
+
+    /// Jump consistent hash (Lamping & Veach) of a pre-hashed `key`,
+    /// e.g. `self.digest(bytes)`.
+    ///
+    /// Growing from `n` to `n + 1` buckets only moves the keys that are
+    /// assigned to the new bucket, about `1 / (n + 1)` of them.
+    /// Panics if `num_buckets` is 0.
+    pub fn jump_hash(&self, key: u64, num_buckets: u32) -> u32 {
+        assert!(num_buckets > 0, "num_buckets must be positive");
+        let mut key = key;
+        let mut b = 0;
+        let mut j = 0;
+        while j < i64::from(num_buckets) {
+            b = j;
+            key = key.wrapping_mul(2862933555777941757).wrapping_add(1);
+            j = ((b + 1) as f64 * ((1u64 << 31) as f64 / ((key >> 33) + 1) as f64)) as i64;
+        }
+        b as u32
+    }
+
+    /// Two distinct buckets in `[0, num_buckets)` for `key`, for the
+    /// "power of two choices" strategy: the caller picks the less loaded
+    /// one. Panics if `num_buckets` is smaller than 2.
+    pub fn two_choices(&self, key: &[u8], num_buckets: u32) -> (u32, u32) {
+        assert!(num_buckets >= 2, "two choices need at least two buckets");
+        let first = self.digest_domain("hash::two_choices", 0, key);
+        let first = fast_range(first, u64::from(num_buckets)) as u32;
+        // pick the second among the other buckets, so it is distinct
+        let second = self.digest_domain("hash::two_choices", 1, key);
+        let second = fast_range(second, u64::from(num_buckets - 1)) as u32;
+        (first, if second >= first { second + 1 } else { second })
+    }
+
+    /// The worker in `[0, workers)` that processes `key`, for splitting
+    /// work reproducibly. Panics if `workers` is 0.
+    #[inline]
+    pub fn partition(&self, key: &[u8], workers: u32) -> u32 {
+        self.bucket_bytes(key, workers)
+    }
+
+    /// Indices of `keys` grouped by the worker `partition` assigns them
+    /// to, in increasing order within each group. Panics if `workers`
+    /// is 0.
+    pub fn partition_balanced(&self, keys: &[&[u8]], workers: u32) -> Vec<Vec<usize>> {
+        assert!(workers > 0, "workers must be positive");
+        let mut groups = vec![Vec::new(); workers as usize];
+        for (i, key) in keys.iter().enumerate() {
+            groups[self.partition(key, workers) as usize].push(i);
+        }
+        groups
+    }
+
+    /// Variant in `[0, num_variants)` of `experiment` assigned to
+    /// `user_id`.
+    ///
+    /// The experiment name is length-framed and hashed with the user id,
+    /// so a user always gets the same variant of an experiment while the
+    /// assignments of different experiments (and of the rollout of a flag
+    /// with the same name) are independent.
+    /// Panics if `num_variants` is 0.
+    pub fn experiment_bucket(&self, user_id: &[u8], experiment: &str, num_variants: u32) -> u32 {
+        assert!(num_variants > 0, "num_variants must be positive");
+        let mut ctx = self.init_domain("hash::experiment_bucket", 0);
+        experiment.hash(&mut ctx);
+        ctx.update(user_id);
+        fast_range(ctx.finish(), u64::from(num_variants)) as u32
+    }
+
+    /// Whether `user_id` is among the `percent`% of users who get `flag`.
+    ///
+    /// The flag name is length-framed and hashed with the user id, so
+    /// the rollouts of different flags (and the experiment with the same
+    /// name) are independent. Each user gets a
+    /// fixed position in `[0, 100)` per flag and is enabled when it is
+    /// below `percent`: raising `percent` only ever adds users.
+    /// A `percent` of 100 or more enables everybody.
+    pub fn in_rollout(&self, flag: &str, user_id: &[u8], percent: u8) -> bool {
+        let mut ctx = self.init_domain("hash::in_rollout", 0);
+        flag.hash(&mut ctx);
+        ctx.update(user_id);
+        fast_range(ctx.finish(), 100) < u64::from(percent)
+    }
+
+    /// Shard of the rate limiter tracking `client_key` during `window`.
+    ///
+    /// A client stays on the same shard within a window, while the
+    /// window number is mixed in so that the sharding rotates between
+    /// windows and a hot client does not always land on the same shard.
+    /// Panics if `num_shards` is 0.
+    #[inline]
+    pub fn rate_limit_shard(&self, client_key: &[u8], num_shards: u32, window: u64) -> u32 {
+        assert!(num_shards > 0, "num_shards must be positive");
+        let hash = self.digest_domain("hash::rate_limit_shard", window, client_key);
+        fast_range(hash, u64::from(num_shards)) as u32
+    }
+}
+
+/// Route `session_id` to one of `backends`, always the same one for a
+/// given session and backend list.
+///
+/// Panics if `backends` is empty.
+pub fn sticky_route<'a, T>(session_id: &[u8], backends: &'a [T], f: &SipHashFunction) -> &'a T {
+    assert!(!backends.is_empty(), "no backend to route to");
+    let i = fast_range(f.digest(session_id), backends.len() as u64);
+    &backends[i as usize]
+}
@@ -0,0 +1,34 @@
+use crate::traits::{HashContext, HashFunction};
+
+/// Tamper-evident hash chain, as used for append-only logs.
+///
+/// Each appended entry is hashed together with the previous head, so
+/// modifying any earlier entry changes every head computed after it.
+pub struct HashChain<H: HashFunction> {
+    f: H,
+    head: u64,
+}
+
+impl<H> HashChain<H>
+    where H: HashFunction, H::Context: HashContext<Result = u64>
+{
+    /// Start an empty chain, whose head is 0.
+    #[inline]
+    pub fn new(f: H) -> HashChain<H> {
+        HashChain { f, head: 0 }
+    }
+
+    /// Append `data`, setting the head to `digest(head || data)`.
+    pub fn append(&mut self, data: &[u8]) -> u64 {
+        let mut ctx = self.f.init();
+        ctx.update(&self.head.to_le_bytes());
+        ctx.update(data);
+        self.head = ctx.finish();
+        self.head
+    }
+
+    #[inline]
+    pub fn head(&self) -> u64 {
+        self.head
+    }
+}
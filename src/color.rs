@@ -0,0 +1,42 @@
+use crate::sampling::unit_f64;
+use crate::sip::SipHashFunction;
+use crate::traits::HashFunction;
+
+// Ranges of the saturation and lightness (in HSL) of the generated
+// colors, avoiding washed out, too dark and too bright ones
+const SATURATION: (f64, f64) = (0.45, 0.80);
+const LIGHTNESS: (f64, f64) = (0.40, 0.60);
+
+impl SipHashFunction {
+    /// Stable RGB color for `id`, e.g. for placeholder avatars.
+    ///
+    /// The hue is uniform over the whole color wheel, while saturation
+    /// and lightness are kept in a moderate range, so that every color
+    /// is readable and distinct ids usually look different.
+    pub fn to_color(&self, id: &[u8]) -> (u8, u8, u8) {
+        let hash = self.digest(id);
+        let hue = unit_f64(hash) * 6.0;
+        // the low bits are (all but) ignored by the hue
+        let lerp = |(lo, hi): (f64, f64), bits: u64| lo + (hi - lo) * (bits & 0x3ff) as f64 / 1023.0;
+        let saturation = lerp(SATURATION, hash);
+        let lightness = lerp(LIGHTNESS, hash >> 10);
+        hsl_to_rgb(hue, saturation, lightness)
+    }
+}
+
+/// Convert a color from HSL, with `hue` in `[0, 6)` sextants, to RGB.
+fn hsl_to_rgb(hue: f64, saturation: f64, lightness: f64) -> (u8, u8, u8) {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+    let (r, g, b) = match hue as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    let channel = |c: f64| ((c + m) * 255.0).round() as u8;
+    (channel(r), channel(g), channel(b))
+}
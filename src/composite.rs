@@ -0,0 +1,49 @@
+use crate::traits::{HashContext, HashFunction};
+
+const TAG_U64: u8 = 1;
+const TAG_STR: u8 = 2;
+const TAG_BYTES: u8 = 3;
+
+/// Builder for stable composite keys made of typed parts.
+///
+/// Each part is framed with a type tag and its length, so that parts
+/// cannot blend into each other and e.g. a `u64` part never collides
+/// with a string part made of the same bytes.
+#[derive(Clone, Debug, Default)]
+pub struct CompositeKey {
+    buf: Vec<u8>,
+}
+
+impl CompositeKey {
+    #[inline]
+    pub fn new() -> CompositeKey {
+        CompositeKey { buf: Vec::new() }
+    }
+
+    #[inline]
+    pub fn add_u64(self, x: u64) -> CompositeKey {
+        self.add(TAG_U64, &x.to_le_bytes())
+    }
+
+    #[inline]
+    pub fn add_str(self, s: &str) -> CompositeKey {
+        self.add(TAG_STR, s.as_bytes())
+    }
+
+    #[inline]
+    pub fn add_bytes(self, b: &[u8]) -> CompositeKey {
+        self.add(TAG_BYTES, b)
+    }
+
+    #[inline]
+    pub fn finish<H: HashFunction>(&self, f: &H) -> <H::Context as HashContext>::Result {
+        f.digest(&self.buf)
+    }
+
+    fn add(mut self, tag: u8, part: &[u8]) -> CompositeKey {
+        self.buf.push(tag);
+        self.buf.extend_from_slice(&(part.len() as u64).to_le_bytes());
+        self.buf.extend_from_slice(part);
+        self
+    }
+}
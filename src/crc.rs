@@ -0,0 +1,201 @@
+use core::convert::TryInto;
+
+use crate::traits::{HashContext, HashFunction, ResettableContext};
+
+/// Reflected CRC-32 (IEEE 802.3) polynomial.
+const IEEE: u32 = 0xedb88320;
+
+/// Reflected CRC-32C (Castagnoli) polynomial.
+const CASTAGNOLI: u32 = 0x82f63b78;
+
+/// Slice-by-8 tables: `t[0]` is the usual byte-at-a-time table and
+/// `t[k][i]` is the CRC of byte `i` followed by `k` zero bytes.
+const fn make_tables(poly: u32) -> [[u32; 256]; 8] {
+    let mut t = [[0u32; 256]; 8];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ poly } else { crc >> 1 };
+            bit += 1;
+        }
+        t[0][i] = crc;
+        i += 1;
+    }
+
+    let mut k = 1;
+    while k < 8 {
+        let mut i = 0;
+        while i < 256 {
+            let prev = t[k - 1][i];
+            t[k][i] = (prev >> 8) ^ t[0][(prev & 0xff) as usize];
+            i += 1;
+        }
+        k += 1;
+    }
+    t
+}
+
+static IEEE_TABLES: [[u32; 256]; 8] = make_tables(IEEE);
+static CASTAGNOLI_TABLES: [[u32; 256]; 8] = make_tables(CASTAGNOLI);
+
+/// Portable slice-by-8 update of the (non-inverted) running `crc`.
+#[inline]
+fn slice_by_8(t: &[[u32; 256]; 8], mut crc: u32, msg: &[u8]) -> u32 {
+    let mut words = msg.chunks_exact(8);
+    for word in &mut words {
+        let lo = u32::from_le_bytes(word[..4].try_into().unwrap()) ^ crc;
+        let hi = u32::from_le_bytes(word[4..].try_into().unwrap());
+        crc = t[7][(lo & 0xff) as usize] ^
+              t[6][((lo >> 8) & 0xff) as usize] ^
+              t[5][((lo >> 16) & 0xff) as usize] ^
+              t[4][(lo >> 24) as usize] ^
+              t[3][(hi & 0xff) as usize] ^
+              t[2][((hi >> 8) & 0xff) as usize] ^
+              t[1][((hi >> 16) & 0xff) as usize] ^
+              t[0][(hi >> 24) as usize];
+    }
+    for &b in words.remainder() {
+        crc = t[0][((crc ^ u32::from(b)) & 0xff) as usize] ^ (crc >> 8);
+    }
+    crc
+}
+
+/// CRC-32C update with the SSE4.2 `crc32` instruction.
+///
+/// Unsafe because: the CPU must support SSE4.2
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+#[target_feature(enable = "sse4.2")]
+unsafe fn castagnoli_sse42(crc: u32, msg: &[u8]) -> u32 {
+    use core::arch::x86_64::{_mm_crc32_u64, _mm_crc32_u8};
+
+    let mut crc = u64::from(crc);
+    let mut words = msg.chunks_exact(8);
+    for word in &mut words {
+        crc = _mm_crc32_u64(crc, u64::from_le_bytes(word.try_into().unwrap()));
+    }
+    let mut crc = crc as u32;
+    for &b in words.remainder() {
+        crc = _mm_crc32_u8(crc, b);
+    }
+    crc
+}
+
+#[inline]
+fn castagnoli(crc: u32, msg: &[u8]) -> u32 {
+    #[cfg(all(feature = "std", target_arch = "x86_64"))]
+    {
+        if std::is_x86_feature_detected!("sse4.2") {
+            return unsafe { castagnoli_sse42(crc, msg) };
+        }
+    }
+    slice_by_8(&CASTAGNOLI_TABLES, crc, msg)
+}
+
+/// CRC-32 (IEEE 802.3), as used by zlib, gzip, PNG and Ethernet.
+///
+/// A checksum for detecting accidental corruption, not a hash for
+/// hashtables: it is linear and trivial to forge.
+#[derive(Clone, Copy, Default)]
+pub struct Crc32HashFunction;
+
+impl Crc32HashFunction {
+    #[inline]
+    pub fn new() -> Crc32HashFunction {
+        Crc32HashFunction
+    }
+}
+
+#[derive(Clone)]
+pub struct Crc32Context {
+    state: u32,
+}
+
+impl HashContext for Crc32Context {
+    type Result = u32;
+
+    #[inline]
+    fn update(&mut self, msg: &[u8]) {
+        self.state = slice_by_8(&IEEE_TABLES, self.state, msg);
+    }
+
+    #[inline]
+    fn finish(self) -> u32 {
+        !self.state
+    }
+}
+
+impl ResettableContext for Crc32Context {
+    #[inline]
+    fn finish_reset(&mut self) -> u32 {
+        let result = !self.state;
+        self.state = !0;
+        result
+    }
+}
+
+impl HashFunction for Crc32HashFunction {
+    type Context = Crc32Context;
+
+    #[inline]
+    fn init(&self) -> Crc32Context {
+        Crc32Context {
+            state: !0,
+        }
+    }
+}
+
+/// CRC-32C (Castagnoli), as used by iSCSI, ext4, Btrfs and SCTP.
+///
+/// On x86_64 with `std`, the SSE4.2 `crc32` instruction is used when the
+/// CPU supports it (detected at runtime); otherwise, and for CRC-32
+/// which has no such instruction, a portable slice-by-8 table is used.
+#[derive(Clone, Copy, Default)]
+pub struct Crc32cHashFunction;
+
+impl Crc32cHashFunction {
+    #[inline]
+    pub fn new() -> Crc32cHashFunction {
+        Crc32cHashFunction
+    }
+}
+
+#[derive(Clone)]
+pub struct Crc32cContext {
+    state: u32,
+}
+
+impl HashContext for Crc32cContext {
+    type Result = u32;
+
+    #[inline]
+    fn update(&mut self, msg: &[u8]) {
+        self.state = castagnoli(self.state, msg);
+    }
+
+    #[inline]
+    fn finish(self) -> u32 {
+        !self.state
+    }
+}
+
+impl ResettableContext for Crc32cContext {
+    #[inline]
+    fn finish_reset(&mut self) -> u32 {
+        let result = !self.state;
+        self.state = !0;
+        result
+    }
+}
+
+impl HashFunction for Crc32cHashFunction {
+    type Context = Crc32cContext;
+
+    #[inline]
+    fn init(&self) -> Crc32cContext {
+        Crc32cContext {
+            state: !0,
+        }
+    }
+}
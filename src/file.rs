@@ -0,0 +1,36 @@
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+use crate::traits::{HashContext, HashFunction};
+
+/// Run `f` on the contents of the file at `path`, memory-mapped.
+///
+/// The mapping is only sound if the file is not truncated or modified
+/// by another process while `f` runs; the hash of a file that changes
+/// concurrently is meaningless anyway.
+pub(crate) fn with_mapped<P, T, F>(path: P, f: F) -> io::Result<T>
+    where P: AsRef<Path>, F: FnOnce(&[u8]) -> T
+{
+    let file = File::open(path)?;
+    // zero-length mappings are rejected on some platforms
+    if file.metadata()?.len() == 0 {
+        return Ok(f(&[]));
+    }
+    let map = unsafe { memmap2::Mmap::map(&file)? };
+    Ok(f(&map))
+}
+
+/// Combine the digests of the `chunk`-sized leaves of a `len`-byte
+/// input, in index order, framed with both sizes.
+pub(crate) fn combine<H>(f: &H, len: usize, chunk: usize, leaves: &[u64]) -> u64
+    where H: HashFunction + ?Sized, H::Context: HashContext<Result = u64>
+{
+    let mut ctx = f.init();
+    ctx.update(&(len as u64).to_le_bytes());
+    ctx.update(&(chunk as u64).to_le_bytes());
+    for leaf in leaves {
+        ctx.update(&leaf.to_le_bytes());
+    }
+    ctx.finish()
+}
@@ -0,0 +1,40 @@
+use crate::sip::SipHashFunction;
+use crate::traits::Hash;
+
+/// Correction factor of the Flajolet-Martin estimator.
+const PHI: f64 = 0.77351;
+
+/// Flajolet-Martin distinct-count sketch.
+///
+/// Each item sets the bit at the position of the lowest set bit of its
+/// SipHash digest; the number of distinct items is estimated from the
+/// lowest bit still unset. Duplicates set the same bit again, so they do
+/// not inflate the count. A single bitmap is very coarse: estimates are
+/// only accurate within a factor of a few.
+pub struct FmSketch {
+    f: SipHashFunction,
+    bitmap: u64,
+}
+
+impl FmSketch {
+    #[inline]
+    pub fn new(f: SipHashFunction) -> FmSketch {
+        FmSketch { f, bitmap: 0 }
+    }
+
+    #[inline]
+    pub fn add<T: Hash + ?Sized>(&mut self, item: &T) {
+        let rank = std::cmp::min(item.digest(&self.f).trailing_zeros(), 63);
+        self.bitmap |= 1 << rank;
+    }
+
+    /// Estimated number of distinct items added, 0 if none was.
+    #[inline]
+    pub fn estimate(&self) -> f64 {
+        if self.bitmap == 0 {
+            return 0.0;
+        }
+        let r = (!self.bitmap).trailing_zeros();
+        2f64.powi(r as i32) / PHI
+    }
+}
@@ -0,0 +1,62 @@
+use crate::traits::{HashContext, HashFunction, ResettableContext};
+
+const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const PRIME: u64 = 0x100000001b3;
+
+/// 64-bit FNV-1a.
+///
+/// Much cheaper than SipHash on short inputs, but unkeyed and trivially
+/// attackable: only use it for trusted keys.
+#[derive(Clone, Copy, Default)]
+pub struct FnvHashFunction;
+
+impl FnvHashFunction {
+    #[inline]
+    pub fn new() -> FnvHashFunction {
+        FnvHashFunction
+    }
+}
+
+#[derive(Clone)]
+pub struct FnvContext {
+    state: u64,
+}
+
+impl HashContext for FnvContext {
+    type Result = u64;
+
+    #[inline(always)]
+    fn update(&mut self, msg: &[u8]) {
+        let mut state = self.state;
+        for &b in msg {
+            state ^= u64::from(b);
+            state = state.wrapping_mul(PRIME);
+        }
+        self.state = state;
+    }
+
+    #[inline(always)]
+    fn finish(self) -> u64 {
+        self.state
+    }
+}
+
+impl ResettableContext for FnvContext {
+    #[inline(always)]
+    fn finish_reset(&mut self) -> u64 {
+        let result = self.state;
+        self.state = OFFSET_BASIS;
+        result
+    }
+}
+
+impl HashFunction for FnvHashFunction {
+    type Context = FnvContext;
+
+    #[inline(always)]
+    fn init(&self) -> FnvContext {
+        FnvContext {
+            state: OFFSET_BASIS,
+        }
+    }
+}
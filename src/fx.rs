@@ -0,0 +1,106 @@
+use core::convert::TryInto;
+
+use crate::traits::{HashContext, HashFunction, ResettableContext};
+
+const SEED: u64 = 0x517cc1b727220a95;
+
+/// FxHash, the hash function used by rustc (as in `rustc-hash` 1.x).
+///
+/// Very cheap, especially on integer keys, but unkeyed and of poor
+/// quality on adversarial inputs: only use it for trusted keys.
+#[derive(Clone, Copy, Default)]
+pub struct FxHashFunction;
+
+impl FxHashFunction {
+    #[inline]
+    pub fn new() -> FxHashFunction {
+        FxHashFunction
+    }
+}
+
+/// Unlike `rustc_hash::FxHasher`, which processes each `write` on its
+/// own, this context buffers partial words, so that the digest does not
+/// depend on how the input is split across `update()` calls. A single
+/// `update()` gives the same result as a single `FxHasher::write`.
+#[derive(Clone)]
+pub struct FxContext {
+    state: u64,
+    tail: [u8; 8],
+    ntail: usize,
+}
+
+impl FxContext {
+    #[inline(always)]
+    fn add(&mut self, word: u64) {
+        self.state = (self.state.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl HashContext for FxContext {
+    type Result = u64;
+
+    #[inline]
+    fn update(&mut self, msg: &[u8]) {
+        let mut msg = msg;
+
+        if self.ntail != 0 {
+            let n = core::cmp::min(8 - self.ntail, msg.len());
+            self.tail[self.ntail..self.ntail + n].copy_from_slice(&msg[..n]);
+            self.ntail += n;
+            msg = &msg[n..];
+            if self.ntail < 8 {
+                return
+            }
+            self.add(u64::from_le_bytes(self.tail));
+            self.ntail = 0;
+        }
+
+        let mut words = msg.chunks_exact(8);
+        for word in &mut words {
+            self.add(u64::from_le_bytes(word.try_into().unwrap()));
+        }
+        let rest = words.remainder();
+        self.tail[..rest.len()].copy_from_slice(rest);
+        self.ntail = rest.len();
+    }
+
+    #[inline]
+    fn finish(mut self) -> u64 {
+        let tail = self.tail;
+        let mut rest = &tail[..self.ntail];
+        if rest.len() >= 4 {
+            self.add(u64::from(u32::from_le_bytes(rest[..4].try_into().unwrap())));
+            rest = &rest[4..];
+        }
+        if rest.len() >= 2 {
+            self.add(u64::from(u16::from_le_bytes(rest[..2].try_into().unwrap())));
+            rest = &rest[2..];
+        }
+        if let Some(&b) = rest.first() {
+            self.add(u64::from(b));
+        }
+        self.state
+    }
+}
+
+impl ResettableContext for FxContext {
+    #[inline]
+    fn finish_reset(&mut self) -> u64 {
+        let result = self.clone().finish();
+        *self = FxHashFunction.init();
+        result
+    }
+}
+
+impl HashFunction for FxHashFunction {
+    type Context = FxContext;
+
+    #[inline(always)]
+    fn init(&self) -> FxContext {
+        FxContext {
+            state: 0,
+            tail: [0; 8],
+            ntail: 0,
+        }
+    }
+}
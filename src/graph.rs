@@ -0,0 +1,65 @@
+use crate::sip::SipHashFunction;
+use crate::traits::{Hash, HashContext, HashFunction};
+
+fn count_distinct(labels: &[u64]) -> usize {
+    let mut labels = labels.to_vec();
+    labels.sort_unstable();
+    labels.dedup();
+    labels.len()
+}
+
+impl SipHashFunction {
+    /// Heuristic isomorphism-invariant digest of an undirected graph with
+    /// nodes `0..num_nodes`.
+    ///
+    /// Node labels start as the node degree and are refined
+    /// Weisfeiler-Lehman style (each label is replaced by the digest of
+    /// itself and the sorted labels of its neighbours) until the number of
+    /// distinct labels stops growing; the final labels are then combined
+    /// in sorted order. Isomorphic graphs always get the same digest, but
+    /// some non-isomorphic graphs do too (e.g. regular graphs of the same
+    /// size and degree), so equal digests are not a proof of isomorphism.
+    ///
+    /// Panics if an edge refers to a node outside `0..num_nodes`.
+    pub fn digest_graph(&self, edges: &[(u32, u32)], num_nodes: u32) -> u64 {
+        let n = num_nodes as usize;
+        let mut adj = vec![Vec::new(); n];
+        for &(a, b) in edges {
+            adj[a as usize].push(b);
+            if a != b {
+                adj[b as usize].push(a);
+            }
+        }
+
+        let mut labels: Vec<u64> = adj.iter().map(|nb| nb.len() as u64).collect();
+        let mut distinct = count_distinct(&labels);
+        for _ in 0..n {
+            labels = adj.iter().zip(&labels).map(|(nb, &label)| {
+                let mut neighbours: Vec<u64> = nb.iter().map(|&u| labels[u as usize]).collect();
+                neighbours.sort_unstable();
+
+                let mut ctx = self.init();
+                label.hash(&mut ctx);
+                for l in neighbours {
+                    l.hash(&mut ctx);
+                }
+                ctx.finish()
+            }).collect();
+
+            let refined = count_distinct(&labels);
+            if refined == distinct {
+                break;
+            }
+            distinct = refined;
+        }
+
+        labels.sort_unstable();
+        let mut ctx = self.init();
+        num_nodes.hash(&mut ctx);
+        (edges.len() as u64).hash(&mut ctx);
+        for l in labels {
+            l.hash(&mut ctx);
+        }
+        ctx.finish()
+    }
+}
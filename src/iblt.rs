@@ -0,0 +1,126 @@
+use crate::sip::SipHashFunction;
+use crate::util::fast_range;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Cell {
+    count: i64,
+    key_sum: u64,
+    hash_sum: u64,
+}
+
+/// Invertible Bloom Lookup Table over `u64` keys, for set reconciliation.
+///
+/// Each key is added to one cell in each of `num_hashes` sub-tables,
+/// chosen with domain-separated SipHash digests. Subtracting the table of
+/// another set cancels the common keys, and `decode` then recovers the
+/// symmetric difference, as long as it is small compared to the number
+/// of cells.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Iblt {
+    cells: Vec<Cell>,
+    num_hashes: u32,
+    k0: u64,
+    k1: u64,
+}
+
+impl Iblt {
+    /// Build a table with `num_cells` cells split across `num_hashes`
+    /// sub-tables; two tables can only be subtracted if they were built
+    /// with the same parameters.
+    ///
+    /// Panics if `num_hashes` is 0 or `num_cells` is smaller.
+    pub fn new_with_keys(num_cells: u32, num_hashes: u32, k0: u64, k1: u64) -> Iblt {
+        assert!(num_hashes > 0, "an IBLT needs at least one hash");
+        assert!(num_cells >= num_hashes, "an IBLT needs a cell per hash");
+        let num_cells = num_cells - num_cells % num_hashes;
+        Iblt {
+            cells: vec![Cell::default(); num_cells as usize],
+            num_hashes,
+            k0,
+            k1,
+        }
+    }
+
+    #[inline]
+    pub fn insert(&mut self, key: u64) {
+        self.add(key, 1);
+    }
+
+    #[inline]
+    pub fn remove(&mut self, key: u64) {
+        self.add(key, -1);
+    }
+
+    /// The table of the difference between the sets of `self` and
+    /// `other`. Panics if the two tables have different parameters.
+    pub fn subtract(&self, other: &Iblt) -> Iblt {
+        assert!(self.cells.len() == other.cells.len() &&
+                self.num_hashes == other.num_hashes &&
+                (self.k0, self.k1) == (other.k0, other.k1),
+                "IBLTs with different parameters");
+        let mut diff = self.clone();
+        for (d, o) in diff.cells.iter_mut().zip(&other.cells) {
+            d.count -= o.count;
+            d.key_sum ^= o.key_sum;
+            d.hash_sum ^= o.hash_sum;
+        }
+        diff
+    }
+
+    /// List the keys only present in the minuend and those only present
+    /// in the subtrahend of a difference table, or `None` if the table
+    /// is too full to be decoded.
+    pub fn decode(&self) -> Option<(Vec<u64>, Vec<u64>)> {
+        let f = self.function();
+        let mut table = self.clone();
+        let mut added = Vec::new();
+        let mut removed = Vec::new();
+
+        loop {
+            let pure = table.cells.iter().find(|c| {
+                (c.count == 1 || c.count == -1) && c.hash_sum == check(&f, c.key_sum)
+            }).cloned();
+            match pure {
+                Some(cell) => {
+                    table.add(cell.key_sum, -cell.count);
+                    if cell.count == 1 {
+                        added.push(cell.key_sum);
+                    } else {
+                        removed.push(cell.key_sum);
+                    }
+                }
+                None => break,
+            }
+        }
+
+        if table.cells.iter().all(|c| *c == Cell::default()) {
+            Some((added, removed))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn function(&self) -> SipHashFunction {
+        SipHashFunction::new_with_keys(self.k0, self.k1)
+    }
+
+    fn add(&mut self, key: u64, count: i64) {
+        let f = self.function();
+        let hash = check(&f, key);
+        let per_hash = self.cells.len() as u32 / self.num_hashes;
+        for i in 0..self.num_hashes {
+            let slot = f.digest_domain("hash::iblt", u64::from(i), &key.to_le_bytes());
+            let pos = i * per_hash + fast_range(slot, u64::from(per_hash)) as u32;
+            let cell = &mut self.cells[pos as usize];
+            cell.count += count;
+            cell.key_sum ^= key;
+            cell.hash_sum ^= hash;
+        }
+    }
+}
+
+#[inline]
+fn check(f: &SipHashFunction, key: u64) -> u64 {
+    f.digest_domain("hash::iblt_check", 0, &key.to_le_bytes())
+}
@@ -0,0 +1,108 @@
+use std::io;
+
+use crate::traits::HashContext;
+
+/// `io::Write` adapter feeding everything written into a `HashContext`,
+/// and forwarding it to an inner writer (by default `io::Sink`, e.g. to
+/// hash a stream with `io::copy` without buffering it).
+///
+/// Only the bytes accepted by the inner writer are hashed.
+pub struct HashWriter<H: HashContext, W = io::Sink> {
+    ctx: H,
+    inner: W,
+}
+
+impl<H: HashContext> HashWriter<H> {
+    #[inline]
+    pub fn new(ctx: H) -> HashWriter<H> {
+        HashWriter::with_writer(ctx, io::sink())
+    }
+}
+
+impl<H: HashContext, W> HashWriter<H, W> {
+    #[inline]
+    pub fn with_writer(ctx: H, inner: W) -> HashWriter<H, W> {
+        HashWriter { ctx, inner }
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> (H, W) {
+        (self.ctx, self.inner)
+    }
+
+    /// Digest of all the bytes written so far. The inner writer is
+    /// dropped: flush it first, or use `into_inner()` to keep it.
+    #[inline]
+    pub fn finish(self) -> H::Result {
+        self.ctx.finish()
+    }
+}
+
+impl<H: HashContext, W: io::Write> io::Write for HashWriter<H, W> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.ctx.update(&buf[..n]);
+        Ok(n)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// `io::Read` adapter feeding everything read from an inner reader into
+/// a `HashContext`, e.g. to compute the digest of a download while
+/// parsing it.
+pub struct HashReader<H: HashContext, R> {
+    ctx: H,
+    inner: R,
+}
+
+impl<H: HashContext, R> HashReader<H, R> {
+    #[inline]
+    pub fn new(ctx: H, inner: R) -> HashReader<H, R> {
+        HashReader { ctx, inner }
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> (H, R) {
+        (self.ctx, self.inner)
+    }
+
+    /// Digest of all the bytes read so far.
+    #[inline]
+    pub fn finish(self) -> H::Result {
+        self.ctx.finish()
+    }
+}
+
+impl<H: HashContext, R: io::Read> io::Read for HashReader<H, R> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.ctx.update(&buf[..n]);
+        Ok(n)
+    }
+}
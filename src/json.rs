@@ -0,0 +1,74 @@
+use serde_json::{Number, Value};
+
+use crate::sip::SipHashFunction;
+use crate::traits::{HashContext, HashFunction};
+
+/// Digest of a JSON document that does not depend on the order of the
+/// keys of its objects.
+///
+/// Objects are hashed with their keys in sorted order, arrays in order,
+/// and every value is prefixed by a type tag (and strings, arrays and
+/// objects by their length), so that semantically equal documents hash
+/// equally. Integers are hashed by value and other numbers by their
+/// `f64` bits, so `1` and `1.0` are distinct.
+pub fn digest_json_canonical(json: &Value, f: &SipHashFunction) -> u64 {
+    let mut ctx = f.init();
+    hash_value(json, &mut ctx);
+    ctx.finish()
+}
+
+fn hash_len<C: HashContext>(len: usize, ctx: &mut C) {
+    ctx.update(&(len as u64).to_le_bytes());
+}
+
+fn hash_str<C: HashContext>(s: &str, ctx: &mut C) {
+    hash_len(s.len(), ctx);
+    ctx.update(s.as_bytes());
+}
+
+fn hash_number<C: HashContext>(n: &Number, ctx: &mut C) {
+    if let Some(u) = n.as_u64() {
+        ctx.update(b"u");
+        ctx.update(&u.to_le_bytes());
+    } else if let Some(i) = n.as_i64() {
+        ctx.update(b"i");
+        ctx.update(&i.to_le_bytes());
+    } else {
+        // serde_json numbers are never NaN or infinite; fold -0.0 into 0.0
+        let x = n.as_f64().unwrap_or(0.0) + 0.0;
+        ctx.update(b"f");
+        ctx.update(&x.to_bits().to_le_bytes());
+    }
+}
+
+fn hash_value<C: HashContext>(value: &Value, ctx: &mut C) {
+    match *value {
+        Value::Null => ctx.update(b"n"),
+        Value::Bool(b) => ctx.update(if b { b"t" } else { b"f" }),
+        Value::Number(ref n) => {
+            ctx.update(b"#");
+            hash_number(n, ctx);
+        }
+        Value::String(ref s) => {
+            ctx.update(b"s");
+            hash_str(s, ctx);
+        }
+        Value::Array(ref items) => {
+            ctx.update(b"a");
+            hash_len(items.len(), ctx);
+            for item in items {
+                hash_value(item, ctx);
+            }
+        }
+        Value::Object(ref map) => {
+            ctx.update(b"o");
+            hash_len(map.len(), ctx);
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            for (key, value) in entries {
+                hash_str(key, ctx);
+                hash_value(value, ctx);
+            }
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use crate::sip::SipHashFunction;
+use crate::traits::HashFunction;
+
+impl SipHashFunction {
+    /// Digest of every length-`k` window of `seq`, in order, i.e.
+    /// `seq.len() - k + 1` digests (none if `k` is 0 or longer than
+    /// `seq`).
+    ///
+    /// SipHash cannot be rolled, so each k-mer is hashed independently in
+    /// `O(k)`: identical k-mers hash equally wherever they occur.
+    pub fn kmer_hashes(&self, seq: &[u8], k: usize) -> Vec<u64> {
+        if k == 0 {
+            return Vec::new();
+        }
+        seq.windows(k).map(|kmer| self.digest(kmer)).collect()
+    }
+
+    /// Minimizers of `seq`: the position and hash of the smallest k-mer
+    /// in each window of `w` consecutive k-mers, ties being broken by the
+    /// leftmost position.
+    ///
+    /// Consecutive windows sharing their minimizer only report it once,
+    /// so the result is sorted by position and is a subset of
+    /// `kmer_hashes`. It is empty if there is no full window.
+    pub fn minimizers(&self, seq: &[u8], k: usize, w: usize) -> Vec<(usize, u64)> {
+        let hashes = self.kmer_hashes(seq, k);
+        let mut minimizers: Vec<(usize, u64)> = Vec::new();
+        if w == 0 {
+            return minimizers;
+        }
+
+        for (start, window) in hashes.windows(w).enumerate() {
+            let (i, &hash) = window.iter().enumerate()
+                .min_by_key(|&(i, &hash)| (hash, i))
+                .unwrap();
+            let pos = start + i;
+            if minimizers.last().map(|&(p, _)| p) != Some(pos) {
+                minimizers.push((pos, hash));
+            }
+        }
+        minimizers
+    }
+}
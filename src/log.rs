@@ -0,0 +1,86 @@
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::traits::{HashContext, HashFunction};
+
+/// Running digest of an append-only file.
+///
+/// Each call to `update_to_end()` memory-maps and hashes only the bytes
+/// appended since the previous call, so the digest always equals
+/// `f.digest()` of the whole file as of the last update.
+pub struct LogHasher<H: HashFunction> {
+    path: PathBuf,
+    offset: u64,
+    ctx: H::Context,
+}
+
+impl<H: HashFunction> LogHasher<H> {
+    pub fn new<P: AsRef<Path>>(f: &H, path: P) -> LogHasher<H> {
+        LogHasher {
+            path: path.as_ref().to_path_buf(),
+            offset: 0,
+            ctx: f.init(),
+        }
+    }
+
+    /// Continue hashing `path` from `offset`, where `ctx` holds the digest
+    /// state of its first `offset` bytes, e.g. as saved by `into_parts()`
+    /// before a restart, without hashing these bytes again.
+    ///
+    /// The prefix is not checked: resuming with a context that does not
+    /// match it silently gives a different digest.
+    pub fn resume<P: AsRef<Path>>(path: P, offset: u64, ctx: H::Context) -> LogHasher<H> {
+        LogHasher {
+            path: path.as_ref().to_path_buf(),
+            offset,
+            ctx,
+        }
+    }
+
+    /// The offset and context to pass to `resume()` to continue hashing
+    /// the file later.
+    #[inline]
+    pub fn into_parts(self) -> (u64, H::Context) {
+        (self.offset, self.ctx)
+    }
+
+    /// Number of bytes of the file hashed so far.
+    #[inline]
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Hash the bytes appended to the file since the last update.
+    ///
+    /// Fails with `InvalidData` if the file is now shorter than the
+    /// hashed prefix, as it is then no longer append-only.
+    pub fn update_to_end(&mut self) -> io::Result<()> {
+        let file = File::open(&self.path)?;
+        let len = file.metadata()?.len();
+        if len < self.offset {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "log file was truncated"));
+        }
+        // zero-length mappings are rejected on some platforms
+        if len == self.offset {
+            return Ok(());
+        }
+
+        let map = unsafe {
+            memmap2::MmapOptions::new()
+                .offset(self.offset)
+                .len((len - self.offset) as usize)
+                .map(&file)?
+        };
+        self.ctx.update(&map);
+        self.offset = len;
+        Ok(())
+    }
+
+    /// Digest of the bytes hashed so far.
+    pub fn digest(&self) -> <H::Context as HashContext>::Result
+        where H::Context: Clone
+    {
+        self.ctx.clone().finish()
+    }
+}
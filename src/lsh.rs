@@ -0,0 +1,39 @@
+use crate::sip::SipHashFunction;
+use crate::traits::HashFunction;
+
+/// Number of differing bits between two digests.
+#[inline]
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+/// Number of differing bits between two byte-array digests.
+///
+/// Panics if the lengths differ.
+pub fn hamming_distance_bytes(a: &[u8], b: &[u8]) -> u32 {
+    assert_eq!(a.len(), b.len(), "digests of different lengths");
+    a.iter().zip(b).map(|(x, y)| (x ^ y).count_ones()).sum()
+}
+
+/// 64-bit SimHash of a set of features.
+///
+/// Each bit of the result is the majority vote of that bit across the
+/// digests of the features, so similar feature sets get SimHashes with a
+/// small Hamming distance.
+pub fn simhash(features: &[&[u8]], f: &SipHashFunction) -> u64 {
+    let mut votes = [0i64; 64];
+    for feature in features {
+        let h = f.digest(feature);
+        for (bit, vote) in votes.iter_mut().enumerate() {
+            if h >> bit & 1 == 1 {
+                *vote += 1;
+            } else {
+                *vote -= 1;
+            }
+        }
+    }
+
+    votes.iter().enumerate()
+        .filter(|&(_, &vote)| vote > 0)
+        .fold(0, |acc, (bit, _)| acc | 1 << bit)
+}
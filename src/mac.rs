@@ -0,0 +1,62 @@
+use crate::sip::{SipHash128Function, SipHashFunction};
+use crate::traits::{HashContext, HashFunction};
+
+/// Keyed SipHash used as a MAC, with a minimum effective input length.
+///
+/// Very short messages leave an attacker who can query tags with only a
+/// handful of possible inputs per length. Messages shorter than
+/// `min_len` are therefore zero-padded up to `min_len` and framed with
+/// their original length, so that e.g. `[0]` and `[0, 0]` cannot collide
+/// through the padding. Messages of at least `min_len` bytes are hashed
+/// as plain SipHash.
+///
+/// Padded messages are hashed with a second key derived from the MAC key,
+/// so a long message that happens to equal the padded encoding of a short
+/// one does not share its tag. The second key is the SipHash-2-4-128
+/// digest of a fixed label: its finalization differs from the 64-bit one,
+/// so no tag reveals it, whatever the message. This does not make SipHash-2-4 stronger
+/// than its 64-bit output: tags can still be brute-forced with ~2^64
+/// attempts, and the key must stay secret.
+pub struct MacSip {
+    f: SipHashFunction,
+    padded: SipHashFunction,
+    min_len: usize,
+}
+
+impl MacSip {
+    pub fn new_with_keys(k0: u64, k1: u64, min_len: usize) -> MacSip {
+        let f = SipHashFunction::new_with_keys(k0, k1);
+        let key = SipHash128Function::new_with_keys(k0, k1).digest(b"hash::mac padding");
+        let padded = SipHashFunction::new_with_keys(key as u64, (key >> 64) as u64);
+        MacSip { f, padded, min_len }
+    }
+
+    #[inline]
+    pub fn min_len(&self) -> usize {
+        self.min_len
+    }
+
+    pub fn digest(&self, bytes: &[u8]) -> u64 {
+        if bytes.len() >= self.min_len {
+            return self.f.digest(bytes);
+        }
+
+        const ZEROS: [u8; 64] = [0; 64];
+
+        let mut ctx = self.padded.init();
+        ctx.update(bytes);
+        let mut pad = self.min_len - bytes.len();
+        while pad > 0 {
+            let n = core::cmp::min(pad, ZEROS.len());
+            ctx.update(&ZEROS[..n]);
+            pad -= n;
+        }
+        ctx.update(&(bytes.len() as u64).to_le_bytes());
+        ctx.finish()
+    }
+
+    #[inline]
+    pub fn verify(&self, bytes: &[u8], tag: u64) -> bool {
+        self.digest(bytes) == tag
+    }
+}
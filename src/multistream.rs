@@ -0,0 +1,32 @@
+use std::collections::HashMap;
+
+use crate::traits::{HashContext, HashFunction};
+
+/// Independent digests of several streams whose data arrives interleaved,
+/// as in a multiplexed protocol.
+///
+/// A separate context is kept for each stream id, so the digest of a
+/// stream equals the digest of its bytes fed contiguously.
+pub struct MultiStreamHasher<H: HashFunction> {
+    f: H,
+    streams: HashMap<u32, H::Context>,
+}
+
+impl<H: HashFunction> MultiStreamHasher<H> {
+    #[inline]
+    pub fn new(f: H) -> MultiStreamHasher<H> {
+        MultiStreamHasher { f, streams: HashMap::new() }
+    }
+
+    /// Feed `bytes` to stream `stream_id`, starting it if needed.
+    pub fn feed(&mut self, stream_id: u32, bytes: &[u8]) {
+        let f = &self.f;
+        self.streams.entry(stream_id).or_insert_with(|| f.init()).update(bytes);
+    }
+
+    /// Finalize stream `stream_id`, or return `None` if it was never fed
+    /// (or already finalized).
+    pub fn finish_stream(&mut self, stream_id: u32) -> Option<<H::Context as HashContext>::Result> {
+        self.streams.remove(&stream_id).map(HashContext::finish)
+    }
+}
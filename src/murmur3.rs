@@ -0,0 +1,286 @@
+use core::convert::TryInto;
+
+use crate::traits::{HashContext, HashFunction, ResettableContext};
+
+const C1_32: u32 = 0xcc9e2d51;
+const C2_32: u32 = 0x1b873593;
+
+const C1_64: u64 = 0x87c37b91114253d5;
+const C2_64: u64 = 0x4cf5ad432745937f;
+
+#[inline(always)]
+fn fmix32(mut h: u32) -> u32 {
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x85ebca6b);
+    h ^= h >> 13;
+    h = h.wrapping_mul(0xc2b2ae35);
+    h ^ (h >> 16)
+}
+
+#[inline(always)]
+fn fmix64(mut k: u64) -> u64 {
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xff51afd7ed558ccd);
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xc4ceb9fe1a85ec53);
+    k ^ (k >> 33)
+}
+
+#[inline(always)]
+fn mix_k1_32(k1: u32) -> u32 {
+    k1.wrapping_mul(C1_32).rotate_left(15).wrapping_mul(C2_32)
+}
+
+#[inline(always)]
+fn mix_k1_64(k1: u64) -> u64 {
+    k1.wrapping_mul(C1_64).rotate_left(31).wrapping_mul(C2_64)
+}
+
+#[inline(always)]
+fn mix_k2_64(k2: u64) -> u64 {
+    k2.wrapping_mul(C2_64).rotate_left(33).wrapping_mul(C1_64)
+}
+
+/// Load the `bytes.len() <= 8` bytes as a partial u64 word, in LE order.
+#[inline(always)]
+fn load_tail(bytes: &[u8]) -> u64 {
+    let mut word = [0u8; 8];
+    word[..bytes.len()].copy_from_slice(bytes);
+    u64::from_le_bytes(word)
+}
+
+/// MurmurHash3_x86_32, bit-exact with the reference implementation.
+///
+/// Fast and well distributed, but unkeyed (the seed is not a secret key)
+/// and vulnerable to hash flooding: use it for interoperability with
+/// existing data, not for untrusted keys. Streaming inputs longer than
+/// 4 GiB give the same result as the reference, which truncates the
+/// length to 32 bits.
+#[derive(Clone, Copy, Default)]
+pub struct Murmur3x32HashFunction {
+    seed: u32,
+}
+
+impl Murmur3x32HashFunction {
+    #[inline]
+    pub fn new() -> Murmur3x32HashFunction {
+        Murmur3x32HashFunction::new_with_seed(0)
+    }
+
+    #[inline]
+    pub fn new_with_seed(seed: u32) -> Murmur3x32HashFunction {
+        Murmur3x32HashFunction { seed }
+    }
+}
+
+#[derive(Clone)]
+pub struct Murmur3x32Context {
+    seed: u32,
+    h1: u32,
+    length: usize,  // how many bytes we've processed
+    tail: [u8; 4],  // unprocessed bytes
+    ntail: usize,   // how many bytes in tail are valid
+}
+
+impl Murmur3x32Context {
+    #[inline(always)]
+    fn block(&mut self, k1: u32) {
+        self.h1 ^= mix_k1_32(k1);
+        self.h1 = self.h1.rotate_left(13).wrapping_mul(5).wrapping_add(0xe6546b64);
+    }
+}
+
+impl HashContext for Murmur3x32Context {
+    type Result = u32;
+
+    fn update(&mut self, msg: &[u8]) {
+        self.length += msg.len();
+
+        let mut msg = msg;
+        if self.ntail != 0 {
+            let n = core::cmp::min(4 - self.ntail, msg.len());
+            self.tail[self.ntail..self.ntail + n].copy_from_slice(&msg[..n]);
+            self.ntail += n;
+            msg = &msg[n..];
+            if self.ntail < 4 {
+                return
+            }
+            self.block(u32::from_le_bytes(self.tail));
+            self.ntail = 0;
+        }
+
+        let mut blocks = msg.chunks_exact(4);
+        for block in &mut blocks {
+            self.block(u32::from_le_bytes(block.try_into().unwrap()));
+        }
+        let rest = blocks.remainder();
+        self.tail[..rest.len()].copy_from_slice(rest);
+        self.ntail = rest.len();
+    }
+
+    fn finish(self) -> u32 {
+        let mut h1 = self.h1;
+        if self.ntail != 0 {
+            h1 ^= mix_k1_32(load_tail(&self.tail[..self.ntail]) as u32);
+        }
+        fmix32(h1 ^ self.length as u32)
+    }
+}
+
+impl ResettableContext for Murmur3x32Context {
+    #[inline]
+    fn finish_reset(&mut self) -> u32 {
+        let result = self.clone().finish();
+        *self = Murmur3x32HashFunction::new_with_seed(self.seed).init();
+        result
+    }
+}
+
+impl HashFunction for Murmur3x32HashFunction {
+    type Context = Murmur3x32Context;
+
+    #[inline]
+    fn init(&self) -> Murmur3x32Context {
+        Murmur3x32Context {
+            seed: self.seed,
+            h1: self.seed,
+            length: 0,
+            tail: [0; 4],
+            ntail: 0,
+        }
+    }
+}
+
+/// MurmurHash3_x64_128, bit-exact with the reference implementation.
+///
+/// The low 64 bits of the result are the first half of the output of
+/// the reference implementation (`h1`), the high 64 bits the second one
+/// (`h2`). Like `Murmur3x32HashFunction`, it is unkeyed.
+#[derive(Clone, Copy, Default)]
+pub struct Murmur3x128HashFunction {
+    seed: u32,
+}
+
+impl Murmur3x128HashFunction {
+    #[inline]
+    pub fn new() -> Murmur3x128HashFunction {
+        Murmur3x128HashFunction::new_with_seed(0)
+    }
+
+    #[inline]
+    pub fn new_with_seed(seed: u32) -> Murmur3x128HashFunction {
+        Murmur3x128HashFunction { seed }
+    }
+}
+
+#[derive(Clone)]
+pub struct Murmur3x128Context {
+    seed: u32,
+    h1: u64,
+    h2: u64,
+    length: usize,  // how many bytes we've processed
+    tail: [u8; 16], // unprocessed bytes
+    ntail: usize,   // how many bytes in tail are valid
+}
+
+impl Murmur3x128Context {
+    #[inline(always)]
+    fn block(&mut self, k1: u64, k2: u64) {
+        self.h1 ^= mix_k1_64(k1);
+        self.h1 = self.h1.rotate_left(27).wrapping_add(self.h2).wrapping_mul(5).wrapping_add(0x52dce729);
+
+        self.h2 ^= mix_k2_64(k2);
+        self.h2 = self.h2.rotate_left(31).wrapping_add(self.h1).wrapping_mul(5).wrapping_add(0x38495ab5);
+    }
+
+    #[inline(always)]
+    fn block_bytes(&mut self, block: &[u8]) {
+        let k1 = u64::from_le_bytes(block[..8].try_into().unwrap());
+        let k2 = u64::from_le_bytes(block[8..16].try_into().unwrap());
+        self.block(k1, k2);
+    }
+}
+
+impl HashContext for Murmur3x128Context {
+    type Result = u128;
+
+    fn update(&mut self, msg: &[u8]) {
+        self.length += msg.len();
+
+        let mut msg = msg;
+        if self.ntail != 0 {
+            let n = core::cmp::min(16 - self.ntail, msg.len());
+            self.tail[self.ntail..self.ntail + n].copy_from_slice(&msg[..n]);
+            self.ntail += n;
+            msg = &msg[n..];
+            if self.ntail < 16 {
+                return
+            }
+            let tail = self.tail;
+            self.block_bytes(&tail);
+            self.ntail = 0;
+        }
+
+        let mut blocks = msg.chunks_exact(16);
+        for block in &mut blocks {
+            self.block_bytes(block);
+        }
+        let rest = blocks.remainder();
+        self.tail[..rest.len()].copy_from_slice(rest);
+        self.ntail = rest.len();
+    }
+
+    // The tail is split into a low word `k1` (bytes 0..8) and a high word
+    // `k2` (bytes 8..16), each mixed only if it has at least one byte
+    fn finish(self) -> u128 {
+        let mut h1 = self.h1;
+        let mut h2 = self.h2;
+
+        let tail = &self.tail[..self.ntail];
+        if tail.len() > 8 {
+            h2 ^= mix_k2_64(load_tail(&tail[8..]));
+        }
+        if !tail.is_empty() {
+            h1 ^= mix_k1_64(load_tail(&tail[..core::cmp::min(tail.len(), 8)]));
+        }
+
+        h1 ^= self.length as u64;
+        h2 ^= self.length as u64;
+
+        h1 = h1.wrapping_add(h2);
+        h2 = h2.wrapping_add(h1);
+
+        h1 = fmix64(h1);
+        h2 = fmix64(h2);
+
+        h1 = h1.wrapping_add(h2);
+        h2 = h2.wrapping_add(h1);
+
+        ((h2 as u128) << 64) | h1 as u128
+    }
+}
+
+impl ResettableContext for Murmur3x128Context {
+    #[inline]
+    fn finish_reset(&mut self) -> u128 {
+        let result = self.clone().finish();
+        *self = Murmur3x128HashFunction::new_with_seed(self.seed).init();
+        result
+    }
+}
+
+impl HashFunction for Murmur3x128HashFunction {
+    type Context = Murmur3x128Context;
+
+    #[inline]
+    fn init(&self) -> Murmur3x128Context {
+        Murmur3x128Context {
+            seed: self.seed,
+            h1: u64::from(self.seed),
+            h2: u64::from(self.seed),
+            length: 0,
+            tail: [0; 16],
+            ntail: 0,
+        }
+    }
+}
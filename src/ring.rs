@@ -0,0 +1,82 @@
+use std::collections::BTreeMap;
+
+use crate::sip::SipHashFunction;
+use crate::traits::{Hash, HashContext, HashFunction};
+
+/// Number of virtual nodes given to a node by `HashRing::add_node`.
+pub const DEFAULT_VNODES: u32 = 160;
+
+/// Consistent-hash ring keyed with SipHash.
+///
+/// Each node is placed on the ring at one position per virtual node, and
+/// a key belongs to the node owning the first position at or after the
+/// digest of the key (wrapping around). Giving a node more virtual nodes
+/// makes it own a proportionally larger share of the keys.
+#[derive(Clone, Debug)]
+pub struct HashRing<N> {
+    k0: u64,
+    k1: u64,
+    positions: BTreeMap<u64, N>,
+}
+
+impl<N: Hash + Clone + PartialEq> HashRing<N> {
+    #[inline]
+    pub fn new() -> HashRing<N> {
+        HashRing::new_with_keys(0, 0)
+    }
+
+    #[inline]
+    pub fn new_with_keys(k0: u64, k1: u64) -> HashRing<N> {
+        HashRing {
+            k0,
+            k1,
+            positions: BTreeMap::new(),
+        }
+    }
+
+    #[inline]
+    pub fn add_node(&mut self, id: N) {
+        self.add_node_weighted(id, DEFAULT_VNODES)
+    }
+
+    /// Place `vnodes` virtual nodes for `id` on the ring.
+    ///
+    /// The positions only depend on the keys, `id` and the vnode index,
+    /// so every ring built with the same nodes routes keys identically.
+    pub fn add_node_weighted(&mut self, id: N, vnodes: u32) {
+        let f = self.function();
+        for i in 0..vnodes {
+            let mut ctx = f.init();
+            id.hash(&mut ctx);
+            i.hash(&mut ctx);
+            // on the (unlikely) collision of two positions, the node
+            // that was added first keeps it
+            self.positions.entry(ctx.finish()).or_insert_with(|| id.clone());
+        }
+    }
+
+    /// Remove all the virtual nodes of `id`.
+    pub fn remove_node(&mut self, id: &N) {
+        self.positions.retain(|_, node| node != id);
+    }
+
+    /// The node owning `key`, or `None` if the ring is empty.
+    pub fn get_node(&self, key: &[u8]) -> Option<&N> {
+        let hash = self.function().digest(key);
+        self.positions.range(hash..).next()
+            .or_else(|| self.positions.iter().next())
+            .map(|(_, node)| node)
+    }
+
+    #[inline]
+    fn function(&self) -> SipHashFunction {
+        SipHashFunction::new_with_keys(self.k0, self.k1)
+    }
+}
+
+impl<N: Hash + Clone + PartialEq> Default for HashRing<N> {
+    #[inline]
+    fn default() -> HashRing<N> {
+        HashRing::new()
+    }
+}
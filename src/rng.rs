@@ -0,0 +1,31 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use crate::traits::{Hash, HashContext, HashFunction};
+
+/// Derive a 32-byte seed from a hash function and a label.
+///
+/// Each 8-byte word of the seed is a separate finalization of `label`,
+/// after a fixed, length-framed domain label and the word index, so the
+/// result is deterministic for a given (keyed) function and label, and
+/// never equals the digest of data hashed by other means.
+pub fn seed_from_hash<H>(f: &H, label: &[u8]) -> [u8; 32]
+    where H: HashFunction, H::Context: HashContext<Result = u64>
+{
+    let mut seed = [0u8; 32];
+    for (i, word) in seed.chunks_mut(8).enumerate() {
+        let mut ctx = f.init();
+        "hash::seed_from_hash".hash(&mut ctx);
+        ctx.write_u64(i as u64);
+        ctx.update(label);
+        word.copy_from_slice(&ctx.finish().to_le_bytes());
+    }
+    seed
+}
+
+/// Build a `StdRng` seeded through `seed_from_hash`.
+pub fn std_rng_from_hash<H>(f: &H, label: &[u8]) -> StdRng
+    where H: HashFunction, H::Context: HashContext<Result = u64>
+{
+    StdRng::from_seed(seed_from_hash(f, label))
+}
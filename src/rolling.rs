@@ -0,0 +1,199 @@
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+
+use crate::traits::{HashContext, HashFunction, ResettableContext};
+
+/// A hash over a sliding window of bytes, updated in constant time as
+/// bytes enter and leave the window.
+pub trait RollingHash {
+    /// Append `byte` to the window, growing it by one byte.
+    fn push(&mut self, byte: u8);
+
+    /// Slide the window by one byte: `new` enters and `old`, which must be
+    /// the byte pushed a window length ago, leaves.
+    fn slide(&mut self, old: u8, new: u8);
+
+    /// Hash of the bytes currently in the window.
+    fn value(&self) -> u64;
+}
+
+/// Random table for the byte substitution, generated with SplitMix64 from
+/// a fixed seed.
+const fn make_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut state = 0x6275_7a68_6173_6821u64;
+    let mut i = 0;
+    while i < 256 {
+        state = state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        table[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    table
+}
+
+static TABLE: [u64; 256] = make_table();
+
+/// Window length used by `rolling_boundaries()`.
+pub const DEFAULT_WINDOW: usize = 48;
+
+/// Buzhash (cyclic polynomial) rolling hash over a `window`-byte window,
+/// e.g. for content-defined chunking.
+///
+/// The hash of a window only depends on the bytes in it, so chunk
+/// boundaries found with `boundaries()` move along with the content when
+/// data is inserted or removed before them. As a `HashFunction`, the
+/// digest of `bytes` is the value of a window holding all of `bytes`
+/// (the window length is only used by `slide()`). The substitution table
+/// is fixed, so boundaries are predictable: this is not a keyed hash.
+#[derive(Clone, Copy)]
+pub struct BuzHash {
+    window: usize,
+}
+
+impl BuzHash {
+    /// Panics if `window` is 0.
+    #[inline]
+    pub fn new(window: usize) -> BuzHash {
+        assert!(window > 0, "window must be positive");
+        BuzHash { window }
+    }
+
+    #[inline]
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Offsets just past every full window of `data` whose hash has all
+    /// the bits of `mask` clear, i.e. the content-defined chunk boundaries
+    /// for an average chunk size of about `mask + 1` bytes (for a mask of
+    /// low bits).
+    #[cfg(feature = "std")]
+    pub fn boundaries(&self, data: &[u8], mask: u64) -> Vec<usize> {
+        let mut ctx = self.init();
+        let mut cuts = Vec::new();
+        for (i, &b) in data.iter().enumerate() {
+            if i < self.window {
+                ctx.push(b);
+            } else {
+                ctx.slide(data[i - self.window], b);
+            }
+            if i + 1 >= self.window && ctx.value() & mask == 0 {
+                cuts.push(i + 1);
+            }
+        }
+        cuts
+    }
+
+    /// Same as `boundaries()` on the bytes read from `reader`, which are
+    /// streamed through a ring buffer holding the current window instead
+    /// of being collected in memory.
+    #[cfg(feature = "std")]
+    pub fn read_boundaries<R: Read>(&self, mut reader: R, mask: u64) -> io::Result<Vec<usize>> {
+        let mut ctx = self.init();
+        let mut ring = vec![0u8; self.window];
+        let mut buf = [0u8; 8192];
+        let mut offset = 0;
+        let mut cuts = Vec::new();
+        loop {
+            let n = match reader.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            for &b in &buf[..n] {
+                let slot = offset % self.window;
+                if offset < self.window {
+                    ctx.push(b);
+                } else {
+                    ctx.slide(ring[slot], b);
+                }
+                ring[slot] = b;
+                offset += 1;
+                if offset >= self.window && ctx.value() & mask == 0 {
+                    cuts.push(offset);
+                }
+            }
+        }
+        Ok(cuts)
+    }
+}
+
+/// Content-defined chunk boundaries of the stream read from `reader`, for
+/// an average chunk size of about `avg_chunk` bytes (rounded up to a
+/// power of two), using a buzhash over `DEFAULT_WINDOW` bytes.
+///
+/// The boundaries are the same as those of `BuzHash::boundaries()` on the
+/// whole stream, but only a window of it is kept in memory.
+/// Panics if `avg_chunk` is 0.
+#[cfg(feature = "std")]
+pub fn rolling_boundaries<R: Read>(reader: R, avg_chunk: usize) -> io::Result<Vec<usize>> {
+    assert!(avg_chunk > 0, "avg_chunk must be positive");
+    let mask = avg_chunk.next_power_of_two() as u64 - 1;
+    BuzHash::new(DEFAULT_WINDOW).read_boundaries(reader, mask)
+}
+
+#[derive(Clone)]
+pub struct BuzHashContext {
+    state: u64,
+    out_rotation: u32, // rotation of a byte leaving the window
+}
+
+impl RollingHash for BuzHashContext {
+    #[inline(always)]
+    fn push(&mut self, byte: u8) {
+        self.state = self.state.rotate_left(1) ^ TABLE[byte as usize];
+    }
+
+    #[inline(always)]
+    fn slide(&mut self, old: u8, new: u8) {
+        self.state = self.state.rotate_left(1) ^
+                     TABLE[old as usize].rotate_left(self.out_rotation) ^
+                     TABLE[new as usize];
+    }
+
+    #[inline(always)]
+    fn value(&self) -> u64 {
+        self.state
+    }
+}
+
+impl HashContext for BuzHashContext {
+    type Result = u64;
+
+    #[inline]
+    fn update(&mut self, msg: &[u8]) {
+        for &b in msg {
+            self.push(b);
+        }
+    }
+
+    #[inline]
+    fn finish(self) -> u64 {
+        self.state
+    }
+}
+
+impl ResettableContext for BuzHashContext {
+    #[inline]
+    fn finish_reset(&mut self) -> u64 {
+        let result = self.state;
+        self.state = 0;
+        result
+    }
+}
+
+impl HashFunction for BuzHash {
+    type Context = BuzHashContext;
+
+    #[inline]
+    fn init(&self) -> BuzHashContext {
+        BuzHashContext {
+            state: 0,
+            out_rotation: (self.window % 64) as u32,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use crate::sip::SipHashFunction;
+use crate::traits::HashFunction;
+use crate::util::fast_range;
+
+/// Map `hash` onto a uniform fraction in `[0, 1)`, keeping the 53 high
+/// bits that fit exactly in the mantissa of an `f64`.
+#[inline]
+pub(crate) fn unit_f64(hash: u64) -> f64 {
+    (hash >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+}
+
+impl SipHashFunction {
+    /// Uniform fraction in `[0, 1)` derived from the digest of `bytes`.
+    ///
+    /// `digest_unit_f64(id) < rate` gives a consistent per-key sampling
+    /// decision. The 53 high bits of the digest are used, rather than
+    /// `digest as f64 / u64::MAX as f64`, because the latter can round up
+    /// to exactly `1.0`.
+    #[inline]
+    pub fn digest_unit_f64(&self, bytes: &[u8]) -> f64 {
+        unit_f64(self.digest(bytes))
+    }
+
+    /// Weighted reservoir sampling key of `item` (A-Res algorithm).
+    ///
+    /// Keeping the `k` items with the largest keys samples `k` items
+    /// with probability proportional to their (positive) `weight`.
+    #[inline]
+    pub fn reservoir_key(&self, item: &[u8], weight: f64) -> f64 {
+        self.digest_unit_f64(item).powf(1.0 / weight)
+    }
+
+    /// Deterministic exponential backoff with jitter.
+    ///
+    /// Returns a delay in `[cap / 2, cap)` where `cap = base_ms * 2^attempt`
+    /// (saturating), reproducible for a given `(key, attempt)` but spread
+    /// across clients with different keys.
+    pub fn jitter(&self, key: &[u8], base_ms: u64, attempt: u32) -> u64 {
+        let cap = 1u64.checked_shl(attempt)
+            .and_then(|m| base_ms.checked_mul(m))
+            .unwrap_or(u64::MAX);
+        let low = cap / 2;
+        let width = cap - low;
+        let hash = self.digest_domain("hash::jitter", u64::from(attempt), key);
+        low + fast_range(hash, width)
+    }
+
+    /// Deterministic train/test split of the indices `0..n`.
+    ///
+    /// Index `i` goes to the test set when the unit fraction of its
+    /// digest is below `test_fraction`, so each assignment only depends
+    /// on the key and on `i`: growing `n` keeps the previous indices on
+    /// the same side of the split.
+    pub fn train_test_split(&self, n: usize, test_fraction: f64) -> (Vec<usize>, Vec<usize>) {
+        (0..n).partition(|&i| unit_f64(self.digest_domain("hash::train_test_split", i as u64, b"")) >= test_fraction)
+    }
+}
@@ -0,0 +1,98 @@
+use crate::sampling::unit_f64;
+use crate::sip::SipHashFunction;
+use crate::traits::HashFunction;
+
+/// Types that can be deterministically generated from a seed, e.g. for
+/// reproducible fixtures in property tests.
+///
+/// Composite types are built by generating each component from its own
+/// `sub_seed`, as the tuple and array impls do.
+pub trait FromHashSeed: Sized {
+    fn from_hash_seed(seed: u64, f: &SipHashFunction) -> Self;
+}
+
+/// The seed of the `index`-th component of a value generated from `seed`.
+#[inline]
+pub fn sub_seed(seed: u64, index: u64, f: &SipHashFunction) -> u64 {
+    f.digest_domain("hash::sub_seed", index, &seed.to_le_bytes())
+}
+
+#[inline]
+fn word(seed: u64, f: &SipHashFunction) -> u64 {
+    f.digest(&seed.to_le_bytes())
+}
+
+macro_rules! impl_from_hash_seed_int {
+    ($($t:ty)*) => ($(
+        impl FromHashSeed for $t {
+            #[inline]
+            fn from_hash_seed(seed: u64, f: &SipHashFunction) -> $t {
+                word(seed, f) as $t
+            }
+        }
+    )*)
+}
+
+impl_from_hash_seed_int! { u8 u16 u32 u64 usize i8 i16 i32 i64 isize }
+
+impl FromHashSeed for u128 {
+    #[inline]
+    fn from_hash_seed(seed: u64, f: &SipHashFunction) -> u128 {
+        u128::from(word(sub_seed(seed, 0, f), f)) |
+        u128::from(word(sub_seed(seed, 1, f), f)) << 64
+    }
+}
+
+impl FromHashSeed for i128 {
+    #[inline]
+    fn from_hash_seed(seed: u64, f: &SipHashFunction) -> i128 {
+        u128::from_hash_seed(seed, f) as i128
+    }
+}
+
+impl FromHashSeed for bool {
+    #[inline]
+    fn from_hash_seed(seed: u64, f: &SipHashFunction) -> bool {
+        word(seed, f) & 1 == 1
+    }
+}
+
+// Floats are uniform in [0, 1), which is what fixtures usually want and
+// avoids generating NaNs and infinities.
+impl FromHashSeed for f64 {
+    #[inline]
+    fn from_hash_seed(seed: u64, f: &SipHashFunction) -> f64 {
+        unit_f64(word(seed, f))
+    }
+}
+
+impl FromHashSeed for f32 {
+    #[inline]
+    fn from_hash_seed(seed: u64, f: &SipHashFunction) -> f32 {
+        (word(seed, f) >> 40) as f32 * (1.0 / (1u32 << 24) as f32)
+    }
+}
+
+impl<T: FromHashSeed, const N: usize> FromHashSeed for [T; N] {
+    fn from_hash_seed(seed: u64, f: &SipHashFunction) -> [T; N] {
+        std::array::from_fn(|i| T::from_hash_seed(sub_seed(seed, i as u64, f), f))
+    }
+}
+
+macro_rules! impl_from_hash_seed_tuple {
+    ($(($($name:ident $idx:tt)+))*) => ($(
+        impl<$($name: FromHashSeed),+> FromHashSeed for ($($name,)+) {
+            #[inline]
+            fn from_hash_seed(seed: u64, f: &SipHashFunction) -> ($($name,)+) {
+                ($($name::from_hash_seed(sub_seed(seed, $idx, f), f),)+)
+            }
+        }
+    )*)
+}
+
+impl_from_hash_seed_tuple! {
+    (A 0)
+    (A 0 B 1)
+    (A 0 B 1 C 2)
+    (A 0 B 1 C 2 D 3)
+}
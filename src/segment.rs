@@ -0,0 +1,62 @@
+use core::mem;
+
+use crate::traits::{HashContext, HashFunction};
+
+/// Streaming hasher emitting an independent digest for every
+/// `segment_len` bytes, for per-segment verification of large streams.
+///
+/// The hash state is reset at each segment boundary, so the digest of
+/// segment `i` equals `f.digest()` of the bytes in that segment.
+pub struct SegmentHasher<H: HashFunction> {
+    f: H,
+    segment_len: usize,
+    ctx: H::Context,
+    filled: usize,
+    index: usize,
+}
+
+impl<H: HashFunction> SegmentHasher<H> {
+    /// Panics if `segment_len` is 0.
+    pub fn new(f: H, segment_len: usize) -> SegmentHasher<H> {
+        assert!(segment_len > 0, "segment_len must be positive");
+        let ctx = f.init();
+        SegmentHasher {
+            f,
+            segment_len,
+            ctx,
+            filled: 0,
+            index: 0,
+        }
+    }
+
+    /// Feed `bytes`, calling `emit(segment_index, digest)` for each
+    /// segment completed by them.
+    pub fn feed<F>(&mut self, bytes: &[u8], mut emit: F)
+        where F: FnMut(usize, <H::Context as HashContext>::Result)
+    {
+        let mut bytes = bytes;
+        while !bytes.is_empty() {
+            let n = core::cmp::min(self.segment_len - self.filled, bytes.len());
+            self.ctx.update(&bytes[..n]);
+            self.filled += n;
+            bytes = &bytes[n..];
+
+            if self.filled == self.segment_len {
+                let ctx = mem::replace(&mut self.ctx, self.f.init());
+                emit(self.index, ctx.finish());
+                self.index += 1;
+                self.filled = 0;
+            }
+        }
+    }
+
+    /// Digest of the trailing partial segment, if any bytes were fed
+    /// since the last complete one.
+    pub fn finish(self) -> Option<(usize, <H::Context as HashContext>::Result)> {
+        if self.filled == 0 {
+            None
+        } else {
+            Some((self.index, self.ctx.finish()))
+        }
+    }
+}
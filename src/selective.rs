@@ -0,0 +1,42 @@
+use crate::traits::{Hash, HashContext, HashFunction};
+
+/// Builder hashing only the fields the caller selects at runtime.
+///
+/// To hash "everything except X" (e.g. a mutable timestamp), feed the
+/// fields in declaration order with `field`, using `skip` (or
+/// `field_if(false, ..)`) for the excluded ones: the digest is then the
+/// same as if the excluded fields did not exist, so values that only
+/// differ in them hash equally.
+pub struct SelectiveHasher<H: HashFunction> {
+    ctx: H::Context,
+}
+
+impl<H: HashFunction> SelectiveHasher<H> {
+    #[inline]
+    pub fn new(f: &H) -> SelectiveHasher<H> {
+        SelectiveHasher { ctx: f.init() }
+    }
+
+    #[inline]
+    pub fn field<T: Hash + ?Sized>(mut self, value: &T) -> SelectiveHasher<H> {
+        value.hash(&mut self.ctx);
+        self
+    }
+
+    #[inline]
+    pub fn field_if<T: Hash + ?Sized>(self, include: bool, value: &T) -> SelectiveHasher<H> {
+        if include { self.field(value) } else { self }
+    }
+
+    /// Exclude a field; this has no effect on the digest and only
+    /// documents the decision at the call site.
+    #[inline]
+    pub fn skip<T: ?Sized>(self, _value: &T) -> SelectiveHasher<H> {
+        self
+    }
+
+    #[inline]
+    pub fn finish(self) -> <H::Context as HashContext>::Result {
+        self.ctx.finish()
+    }
+}
@@ -0,0 +1,155 @@
+use core::convert::TryInto;
+
+use crate::traits::{HashContext, HashFunction, ResettableContext};
+
+const H0: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a,
+    0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+const BLOCK: usize = 64;
+
+/// SHA-256 (FIPS 180-4).
+///
+/// A cryptographic hash with a 32-byte result, for content addressing
+/// and integrity checks; much slower than the non-cryptographic
+/// functions of this crate.
+#[derive(Clone, Copy, Default)]
+pub struct Sha256HashFunction;
+
+impl Sha256HashFunction {
+    #[inline]
+    pub fn new() -> Sha256HashFunction {
+        Sha256HashFunction
+    }
+}
+
+#[derive(Clone)]
+pub struct Sha256Context {
+    state: [u32; 8],
+    buf: [u8; BLOCK], // unprocessed bytes
+    nbuf: usize,      // how many bytes in buf are valid
+    length: u64,      // how many bytes we've processed
+}
+
+/// Process one 64-byte block.
+fn compress(state: &mut [u32; 8], block: &[u8]) {
+    debug_assert!(block.len() == BLOCK);
+    let mut w = [0u32; 64];
+    for (w, word) in w.iter_mut().zip(block.chunks_exact(4)) {
+        *w = u32::from_be_bytes(word.try_into().unwrap());
+    }
+    for t in 16..64 {
+        let s0 = w[t - 15].rotate_right(7) ^ w[t - 15].rotate_right(18) ^ (w[t - 15] >> 3);
+        let s1 = w[t - 2].rotate_right(17) ^ w[t - 2].rotate_right(19) ^ (w[t - 2] >> 10);
+        w[t] = w[t - 16].wrapping_add(s0).wrapping_add(w[t - 7]).wrapping_add(s1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for t in 0..64 {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let t1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(K[t]).wrapping_add(w[t]);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let t2 = s0.wrapping_add(maj);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+    }
+
+    for (s, v) in state.iter_mut().zip(&[a, b, c, d, e, f, g, h]) {
+        *s = s.wrapping_add(*v);
+    }
+}
+
+impl HashContext for Sha256Context {
+    type Result = [u8; 32];
+
+    fn update(&mut self, msg: &[u8]) {
+        self.length += msg.len() as u64;
+
+        let mut msg = msg;
+        if self.nbuf != 0 {
+            let needed = BLOCK - self.nbuf;
+            if msg.len() < needed {
+                self.buf[self.nbuf..self.nbuf + msg.len()].copy_from_slice(msg);
+                self.nbuf += msg.len();
+                return
+            }
+
+            self.buf[self.nbuf..].copy_from_slice(&msg[..needed]);
+            compress(&mut self.state, &self.buf);
+            self.nbuf = 0;
+            msg = &msg[needed..];
+        }
+
+        let mut blocks = msg.chunks_exact(BLOCK);
+        for block in &mut blocks {
+            compress(&mut self.state, block);
+        }
+        let left = blocks.remainder();
+        self.buf[..left.len()].copy_from_slice(left);
+        self.nbuf = left.len();
+    }
+
+    // Padding is a 0x80 byte, zeros up to 56 bytes mod 64 and the
+    // message length in bits as a big-endian u64
+    fn finish(self) -> [u8; 32] {
+        let mut ctx = self;
+        let bits = ctx.length.wrapping_mul(8);
+
+        let mut pad = [0u8; BLOCK + 8];
+        pad[0] = 0x80;
+        let zeros = (BLOCK + 55 - ctx.nbuf) % BLOCK;
+        pad[1 + zeros..9 + zeros].copy_from_slice(&bits.to_be_bytes());
+        ctx.update(&pad[..9 + zeros]);
+        debug_assert!(ctx.nbuf == 0);
+
+        let mut out = [0u8; 32];
+        for (o, s) in out.chunks_exact_mut(4).zip(&ctx.state) {
+            o.copy_from_slice(&s.to_be_bytes());
+        }
+        out
+    }
+}
+
+impl ResettableContext for Sha256Context {
+    #[inline]
+    fn finish_reset(&mut self) -> [u8; 32] {
+        let result = self.clone().finish();
+        *self = Sha256HashFunction.init();
+        result
+    }
+}
+
+impl HashFunction for Sha256HashFunction {
+    type Context = Sha256Context;
+
+    #[inline]
+    fn init(&self) -> Sha256Context {
+        Sha256Context {
+            state: H0,
+            buf: [0; BLOCK],
+            nbuf: 0,
+            length: 0,
+        }
+    }
+}
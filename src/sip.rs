@@ -1,28 +1,123 @@
-use std::ptr;
+use core::ptr;
+#[cfg(feature = "std")]
+use std::cell::Cell;
+#[cfg(feature = "std")]
+use std::collections::hash_map::RandomState;
 
-use ::traits::{HashContext, HashFunction};
+use crate::traits::{Hash, HashContext, HashFunction, ResettableContext};
 
-pub struct SipHashFunction {
+/// SipHash-c-d: `C` compression rounds per message word and `D`
+/// finalization rounds.
+#[derive(Clone, Copy)]
+pub struct SipHashFunctionCD<const C: usize, const D: usize> {
     k0: u64,
     k1: u64,
 }
 
-impl SipHashFunction {
+/// The standard SipHash-2-4.
+pub type SipHashFunction = SipHashFunctionCD<2, 4>;
+
+/// SipHash-1-3, the faster variant used by Rust's `DefaultHasher`.
+pub type Sip13HashFunction = SipHashFunctionCD<1, 3>;
+
+impl<const C: usize, const D: usize> SipHashFunctionCD<C, D> {
     #[inline]
-    pub fn new() -> SipHashFunction {
-        SipHashFunction::new_with_keys(0, 0)
+    pub fn new() -> SipHashFunctionCD<C, D> {
+        SipHashFunctionCD::new_with_keys(0, 0)
     }
 
     #[inline]
-    pub fn new_with_keys(k0: u64, k1: u64) -> SipHashFunction {
-        SipHashFunction {
-            k0: k0,
-            k1: k1,
+    pub fn new_with_keys(k0: u64, k1: u64) -> SipHashFunctionCD<C, D> {
+        SipHashFunctionCD {
+            k0,
+            k1,
         }
     }
 }
 
-pub struct SipContext {
+impl<const C: usize, const D: usize> SipHashFunctionCD<C, D> {
+    #[inline]
+    pub fn from_key(key: &Key) -> SipHashFunctionCD<C, D> {
+        SipHashFunctionCD::new_with_keys(key.k0, key.k1)
+    }
+
+    /// Function with a fresh random key, see `Key::random`.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn random() -> SipHashFunctionCD<C, D> {
+        SipHashFunctionCD::from_key(&Key::random())
+    }
+
+    /// Function with a key drawn from `rng`, see `Key::from_rng`.
+    #[cfg(feature = "rand")]
+    #[inline]
+    pub fn from_rng<R: rand::RngCore + ?Sized>(rng: &mut R) -> SipHashFunctionCD<C, D> {
+        SipHashFunctionCD::from_key(&Key::from_rng(rng))
+    }
+}
+
+/// A 128-bit SipHash key.
+///
+/// SipHash is only a PRF (and only resists hash-flooding) when its key
+/// is secret and unpredictable: `random()` and `from_rng()` are the
+/// usual ways to build one, `from_bytes()` loads a stored key.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    k0: u64,
+    k1: u64,
+}
+
+impl Key {
+    #[inline]
+    pub fn new(k0: u64, k1: u64) -> Key {
+        Key { k0, k1 }
+    }
+
+    /// Key from its 16-byte encoding: `k0` then `k1`, in LE order, as in
+    /// the reference implementation.
+    #[inline]
+    pub fn from_bytes(bytes: [u8; 16]) -> Key {
+        let mut k0 = [0u8; 8];
+        let mut k1 = [0u8; 8];
+        k0.copy_from_slice(&bytes[..8]);
+        k1.copy_from_slice(&bytes[8..]);
+        Key::new(u64::from_le_bytes(k0), u64::from_le_bytes(k1))
+    }
+
+    #[inline]
+    pub fn to_bytes(&self) -> [u8; 16] {
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&self.k0.to_le_bytes());
+        bytes[8..].copy_from_slice(&self.k1.to_le_bytes());
+        bytes
+    }
+
+    /// Fresh key seeded from the OS randomness, like std's `RandomState`.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn random() -> Key {
+        let (k0, k1) = random_keys();
+        Key::new(k0, k1)
+    }
+
+    /// Key drawn from `rng`, which should be a cryptographically secure
+    /// generator (e.g. `rand::rngs::OsRng`) for DoS resistance.
+    #[cfg(feature = "rand")]
+    #[inline]
+    pub fn from_rng<R: rand::RngCore + ?Sized>(rng: &mut R) -> Key {
+        Key::new(rng.next_u64(), rng.next_u64())
+    }
+}
+
+impl<const C: usize, const D: usize> Default for SipHashFunctionCD<C, D> {
+    #[inline]
+    fn default() -> SipHashFunctionCD<C, D> {
+        SipHashFunctionCD::new()
+    }
+}
+
+#[derive(Clone)]
+pub struct SipContextCD<const C: usize, const D: usize> {
     length: usize, // how many bytes we've processed
     v0: u64,      // hash state
     v2: u64,
@@ -30,29 +125,12 @@ pub struct SipContext {
     v3: u64,
     tail: u64, // unprocessed bytes le
     ntail: usize,  // how many bytes in tail are valid
+    initial: [u64; 4], // hash state right after init, for finish_reset
 }
 
-macro_rules! u8to64_le {
-    ($buf:expr, $i:expr) =>
-    ($buf[0+$i] as u64 |
-     ($buf[1+$i] as u64) << 8 |
-     ($buf[2+$i] as u64) << 16 |
-     ($buf[3+$i] as u64) << 24 |
-     ($buf[4+$i] as u64) << 32 |
-     ($buf[5+$i] as u64) << 40 |
-     ($buf[6+$i] as u64) << 48 |
-     ($buf[7+$i] as u64) << 56);
-    ($buf:expr, $i:expr, $len:expr) =>
-    ({
-        let mut t = 0;
-        let mut out = 0;
-        while t < $len {
-            out |= ($buf[t+$i] as u64) << t*8;
-            t += 1;
-        }
-        out
-    });
-}
+pub type SipContext = SipContextCD<2, 4>;
+
+pub type Sip13Context = SipContextCD<1, 3>;
 
 /// Load a full u64 word from a byte stream, in LE order. Use
 /// `copy_nonoverlapping` to let the compiler generate the most efficient way
@@ -68,6 +146,72 @@ unsafe fn load_u64_le(buf: &[u8], i: usize) -> u64 {
     data.to_le()
 }
 
+/// Load a u32 word from a byte stream, in LE order.
+///
+/// Unsafe because: unchecked indexing at i..i+4
+#[inline]
+unsafe fn load_u32_le(buf: &[u8], i: usize) -> u32 {
+    debug_assert!(i + 4 <= buf.len());
+    let mut data = 0u32;
+    ptr::copy_nonoverlapping(buf.get_unchecked(i),
+                             &mut data as *mut _ as *mut u8, 4);
+    data.to_le()
+}
+
+/// Load a u16 word from a byte stream, in LE order.
+///
+/// Unsafe because: unchecked indexing at i..i+2
+#[inline]
+unsafe fn load_u16_le(buf: &[u8], i: usize) -> u16 {
+    debug_assert!(i + 2 <= buf.len());
+    let mut data = 0u16;
+    ptr::copy_nonoverlapping(buf.get_unchecked(i),
+                             &mut data as *mut _ as *mut u8, 2);
+    data.to_le()
+}
+
+/// Load the `len < 8` bytes at `buf[i..i+len]` as a partial u64 word, in LE
+/// order.
+///
+/// If 8 bytes starting at `i` or ending at `i + len` lie in `buf`, this is
+/// a single unaligned u64 load overlapping the requested bytes, masked (or
+/// shifted) down to them. Otherwise it combines at most one u32, one u16
+/// and one u8 load.
+///
+/// Unsafe because: unchecked indexing at i..i+len
+#[inline]
+unsafe fn u8to64_le(buf: &[u8], i: usize, len: usize) -> u64 {
+    debug_assert!(len < 8 && i + len <= buf.len());
+    if len == 0 {
+        return 0;
+    }
+
+    if i + 8 <= buf.len() {
+        let data = load_u64_le(buf, i);
+        return data & ((1 << (8 * len)) - 1);
+    }
+    if i + len >= 8 {
+        // load the word ending with the requested bytes
+        let data = load_u64_le(buf, i + len - 8);
+        return data >> (8 * (8 - len));
+    }
+
+    let mut t = 0;
+    let mut out = 0;
+    if t + 3 < len {
+        out = u64::from(load_u32_le(buf, i + t));
+        t += 4;
+    }
+    if t + 1 < len {
+        out |= u64::from(load_u16_le(buf, i + t)) << (8 * t);
+        t += 2;
+    }
+    if t < len {
+        out |= u64::from(*buf.get_unchecked(i + t)) << (8 * t);
+    }
+    out
+}
+
 macro_rules! rotl {
     ($x:expr, $b:expr) =>
     (($x << $b) | ($x >> (64_i32.wrapping_sub($b))))
@@ -98,52 +242,73 @@ macro_rules! compress {
         })
 }
 
-impl HashContext for SipContext {
+impl<const C: usize, const D: usize> HashContext for SipContextCD<C, D> {
     type Result = u64;
 
     #[inline(always)]
     fn update(&mut self, msg: &[u8]) {
         let length = msg.len();
+        if length == 0 {
+            return
+        }
         self.length += length;
 
+        // Fast path: nothing buffered and only whole words, so the tail
+        // bookkeeping can be skipped entirely.
+        if self.ntail == 0 && length & 0x7 == 0 {
+            self.compress_words(msg, 0, length);
+            return
+        }
+
         let mut needed = 0;
 
         if self.ntail != 0 {
             needed = 8 - self.ntail;
             if length < needed {
-                self.tail |= u8to64_le!(msg, 0, length) << 8*self.ntail;
+                self.tail |= unsafe { u8to64_le(msg, 0, length) } << (8*self.ntail);
                 self.ntail += length;
                 return
             }
 
-            let m = self.tail | u8to64_le!(msg, 0, needed) << 8*self.ntail;
+            let m = self.tail | (unsafe { u8to64_le(msg, 0, needed) } << (8*self.ntail));
 
             self.v3 ^= m;
-            compress!(self.v0, self.v1, self.v2, self.v3);
-            compress!(self.v0, self.v1, self.v2, self.v3);
+            for _ in 0..C {
+                compress!(self.v0, self.v1, self.v2, self.v3);
+            }
             self.v0 ^= m;
 
             self.ntail = 0;
         }
 
         // Buffered tail is now flushed, process new input.
-        let len = length - needed;
-        let left = len & 0x7;
+        let left = (length - needed) & 0x7;
 
-        let mut i = needed;
-        while i < len - left {
-            let mi = unsafe { load_u64_le(msg, i) };
+        let i = length - left;
+        self.compress_words(msg, needed, i);
 
-            self.v3 ^= mi;
-            compress!(self.v0, self.v1, self.v2, self.v3);
-            compress!(self.v0, self.v1, self.v2, self.v3);
-            self.v0 ^= mi;
+        self.tail = unsafe { u8to64_le(msg, i, left) };
+        self.ntail = left;
+    }
 
-            i += 8;
-        }
+    #[inline(always)]
+    fn write_u8(&mut self, value: u8) {
+        self.short_write(u64::from(value), 1)
+    }
 
-        self.tail = u8to64_le!(msg, i, left);
-        self.ntail = left;
+    #[inline(always)]
+    fn write_u16(&mut self, value: u16) {
+        self.short_write(u64::from(value), 2)
+    }
+
+    #[inline(always)]
+    fn write_u32(&mut self, value: u32) {
+        self.short_write(u64::from(value), 4)
+    }
+
+    #[inline(always)]
+    fn write_u64(&mut self, value: u64) {
+        self.short_write(value, 8)
     }
 
     #[inline(always)]
@@ -156,33 +321,768 @@ impl HashContext for SipContext {
         let b: u64 = ((self.length as u64 & 0xff) << 56) | self.tail;
 
         v3 ^= b;
-        compress!(v0, v1, v2, v3);
-        compress!(v0, v1, v2, v3);
+        for _ in 0..C {
+            compress!(v0, v1, v2, v3);
+        }
         v0 ^= b;
 
         v2 ^= 0xff;
-        compress!(v0, v1, v2, v3);
-        compress!(v0, v1, v2, v3);
-        compress!(v0, v1, v2, v3);
-        compress!(v0, v1, v2, v3);
+        for _ in 0..D {
+            compress!(v0, v1, v2, v3);
+        }
 
         v0 ^ v1 ^ v2 ^ v3
     }
 }
 
-impl HashFunction for SipHashFunction {
-    type Context = SipContext;
-    
+impl<const C: usize, const D: usize> ResettableContext for SipContextCD<C, D> {
+    #[inline(always)]
+    fn finish_reset(&mut self) -> u64 {
+        let result = self.clone().finish();
+        self.reset();
+        result
+    }
+}
+
+impl<const C: usize, const D: usize> SipContextCD<C, D> {
+    /// Compress the whole words `msg[start..end]`.
+    ///
+    /// Each compression depends on the previous state, so the words
+    /// cannot be processed in parallel; instead the state is kept in
+    /// locals and the loop is unrolled to 4 words (32 bytes) per
+    /// iteration, which lets the loads be scheduled ahead of the rounds.
+    #[inline(always)]
+    fn compress_words(&mut self, msg: &[u8], start: usize, end: usize) {
+        debug_assert!(start <= end && end <= msg.len() && (end - start) & 0x7 == 0);
+        let mut v0 = self.v0;
+        let mut v1 = self.v1;
+        let mut v2 = self.v2;
+        let mut v3 = self.v3;
+
+        macro_rules! word {
+            ($m:expr) => ({
+                let m = $m;
+                v3 ^= m;
+                for _ in 0..C {
+                    compress!(v0, v1, v2, v3);
+                }
+                v0 ^= m;
+            })
+        }
+
+        let mut i = start;
+        while i + 32 <= end {
+            let (m0, m1, m2, m3) = unsafe {
+                (load_u64_le(msg, i), load_u64_le(msg, i + 8),
+                 load_u64_le(msg, i + 16), load_u64_le(msg, i + 24))
+            };
+            word!(m0);
+            word!(m1);
+            word!(m2);
+            word!(m3);
+            i += 32;
+        }
+        while i < end {
+            word!(unsafe { load_u64_le(msg, i) });
+            i += 8;
+        }
+
+        self.v0 = v0;
+        self.v1 = v1;
+        self.v2 = v2;
+        self.v3 = v3;
+    }
+
+    /// Feed the `size <= 8` low bytes of `x`, in LE order. Like libstd's
+    /// `short_write`, this merges them into the tail with shifts instead
+    /// of going through a byte slice.
     #[inline(always)]
-    fn init(&self) -> SipContext {
-        SipContext {
-            v0: self.k0 ^ 0x736f6d6570736575,
-            v1: self.k1 ^ 0x646f72616e646f6d,
-            v2: self.k0 ^ 0x6c7967656e657261,
-            v3: self.k1 ^ 0x7465646279746573,
+    fn short_write(&mut self, x: u64, size: usize) {
+        debug_assert!(size <= 8);
+        self.length += size;
+
+        let needed = 8 - self.ntail;
+        self.tail |= x << (8 * self.ntail);
+        if size < needed {
+            self.ntail += size;
+            return
+        }
+
+        let m = self.tail;
+        self.v3 ^= m;
+        for _ in 0..C {
+            compress!(self.v0, self.v1, self.v2, self.v3);
+        }
+        self.v0 ^= m;
+
+        self.ntail = size - needed;
+        self.tail = if needed < 8 { x >> (8 * needed) } else { 0 };
+    }
+
+    #[inline(always)]
+    fn reset(&mut self) {
+        let [v0, v1, v2, v3] = self.initial;
+        self.v0 = v0;
+        self.v1 = v1;
+        self.v2 = v2;
+        self.v3 = v3;
+        self.length = 0;
+        self.tail = 0;
+        self.ntail = 0;
+    }
+}
+
+impl<const C: usize, const D: usize> HashFunction for SipHashFunctionCD<C, D> {
+    type Context = SipContextCD<C, D>;
+
+    #[inline(always)]
+    fn init(&self) -> SipContextCD<C, D> {
+        let v0 = self.k0 ^ 0x736f6d6570736575;
+        let v1 = self.k1 ^ 0x646f72616e646f6d;
+        let v2 = self.k0 ^ 0x6c7967656e657261;
+        let v3 = self.k1 ^ 0x7465646279746573;
+        SipContextCD {
+            v0,
+            v1,
+            v2,
+            v3,
             length: 0,
             tail: 0,
             ntail: 0,
+            initial: [v0, v1, v2, v3],
         }
     }
+
+    // One-shot version of `init() + update() + finish()`: the whole input
+    // is known, so the words are compressed straight from `bytes` and
+    // only the final partial word is loaded, without any tail buffering.
+    #[inline]
+    fn digest(&self, bytes: &[u8]) -> u64 {
+        let mut ctx = self.init();
+        let length = bytes.len();
+        let left = length & 0x7;
+
+        let i = length - left;
+        ctx.compress_words(bytes, 0, i);
+
+        ctx.length = length;
+        ctx.tail = unsafe { u8to64_le(bytes, i, left) };
+        ctx.finish()
+    }
+
+    // Same as `digest()`, the u64 length prefix being a whole word
+    // compressed before the words of `bytes`.
+    #[inline]
+    fn digest_prefixed(&self, bytes: &[u8]) -> u64 {
+        let mut ctx = self.init();
+        let length = bytes.len();
+        let left = length & 0x7;
+
+        ctx.short_write(length as u64, 8);
+        let i = length - left;
+        ctx.compress_words(bytes, 0, i);
+
+        ctx.length = 8 + length;
+        ctx.tail = unsafe { u8to64_le(bytes, i, left) };
+        ctx.finish()
+    }
+}
+
+/// SipHash-c-d with the 128-bit output of the reference implementation.
+#[derive(Clone, Copy)]
+pub struct SipHash128FunctionCD<const C: usize, const D: usize>(SipHashFunctionCD<C, D>);
+
+/// The standard SipHash-2-4 with 128-bit output.
+pub type SipHash128Function = SipHash128FunctionCD<2, 4>;
+
+impl<const C: usize, const D: usize> SipHash128FunctionCD<C, D> {
+    #[inline]
+    pub fn new() -> SipHash128FunctionCD<C, D> {
+        SipHash128FunctionCD::new_with_keys(0, 0)
+    }
+
+    #[inline]
+    pub fn new_with_keys(k0: u64, k1: u64) -> SipHash128FunctionCD<C, D> {
+        SipHash128FunctionCD(SipHashFunctionCD::new_with_keys(k0, k1))
+    }
+
+    #[inline]
+    pub fn from_key(key: &Key) -> SipHash128FunctionCD<C, D> {
+        SipHash128FunctionCD(SipHashFunctionCD::from_key(key))
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn random() -> SipHash128FunctionCD<C, D> {
+        SipHash128FunctionCD(SipHashFunctionCD::random())
+    }
+
+    #[cfg(feature = "rand")]
+    #[inline]
+    pub fn from_rng<R: rand::RngCore + ?Sized>(rng: &mut R) -> SipHash128FunctionCD<C, D> {
+        SipHash128FunctionCD(SipHashFunctionCD::from_rng(rng))
+    }
+}
+
+impl<const C: usize, const D: usize> Default for SipHash128FunctionCD<C, D> {
+    #[inline]
+    fn default() -> SipHash128FunctionCD<C, D> {
+        SipHash128FunctionCD::new()
+    }
+}
+
+/// Same compression as `SipContextCD`, only the initialization and the
+/// finalization differ.
+#[derive(Clone)]
+pub struct SipContext128CD<const C: usize, const D: usize>(SipContextCD<C, D>);
+
+pub type SipContext128 = SipContext128CD<2, 4>;
+
+impl<const C: usize, const D: usize> HashContext for SipContext128CD<C, D> {
+    type Result = u128;
+
+    #[inline(always)]
+    fn update(&mut self, msg: &[u8]) {
+        self.0.update(msg)
+    }
+
+    #[inline(always)]
+    fn write_u8(&mut self, value: u8) {
+        self.0.write_u8(value)
+    }
+
+    #[inline(always)]
+    fn write_u16(&mut self, value: u16) {
+        self.0.write_u16(value)
+    }
+
+    #[inline(always)]
+    fn write_u32(&mut self, value: u32) {
+        self.0.write_u32(value)
+    }
+
+    #[inline(always)]
+    fn write_u64(&mut self, value: u64) {
+        self.0.write_u64(value)
+    }
+
+    // The low 64 bits of the result are the first half of the output of
+    // the reference implementation, the high 64 bits the second one.
+    #[inline(always)]
+    fn finish(self) -> u128 {
+        let ctx = self.0;
+        let mut v0 = ctx.v0;
+        let mut v1 = ctx.v1;
+        let mut v2 = ctx.v2;
+        let mut v3 = ctx.v3;
+
+        let b: u64 = ((ctx.length as u64 & 0xff) << 56) | ctx.tail;
+
+        v3 ^= b;
+        for _ in 0..C {
+            compress!(v0, v1, v2, v3);
+        }
+        v0 ^= b;
+
+        v2 ^= 0xee;
+        for _ in 0..D {
+            compress!(v0, v1, v2, v3);
+        }
+        let lo = v0 ^ v1 ^ v2 ^ v3;
+
+        v1 ^= 0xdd;
+        for _ in 0..D {
+            compress!(v0, v1, v2, v3);
+        }
+        let hi = v0 ^ v1 ^ v2 ^ v3;
+
+        ((hi as u128) << 64) | lo as u128
+    }
+}
+
+impl<const C: usize, const D: usize> ResettableContext for SipContext128CD<C, D> {
+    #[inline(always)]
+    fn finish_reset(&mut self) -> u128 {
+        let result = self.clone().finish();
+        self.0.reset();
+        result
+    }
+}
+
+impl<const C: usize, const D: usize> HashFunction for SipHash128FunctionCD<C, D> {
+    type Context = SipContext128CD<C, D>;
+
+    #[inline(always)]
+    fn init(&self) -> SipContext128CD<C, D> {
+        let mut ctx = self.0.init();
+        ctx.v1 ^= 0xee;
+        ctx.initial[1] = ctx.v1;
+        SipContext128CD(ctx)
+    }
+}
+
+/// Digest of `SipVariable`, in the width that was requested.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SipOutput {
+    U64(u64),
+    /// The 16 bytes output by the reference implementation, i.e. the
+    /// little-endian bytes of the `SipHash128Function` digest.
+    U128([u8; 16]),
+}
+
+/// SipHash-2-4 whose output width is chosen at runtime, e.g. from a flag
+/// stored by a serialization format.
+///
+/// The two widths are computed by `SipHashFunction` and
+/// `SipHash128Function` with the same key; they are unrelated digests,
+/// the 64-bit one is not a truncation of the 128-bit one.
+#[derive(Clone, Copy)]
+pub struct SipVariable {
+    k0: u64,
+    k1: u64,
+}
+
+impl SipVariable {
+    #[inline]
+    pub fn new_with_keys(k0: u64, k1: u64) -> SipVariable {
+        SipVariable { k0, k1 }
+    }
+
+    #[inline]
+    pub fn from_key(key: &Key) -> SipVariable {
+        SipVariable::new_with_keys(key.k0, key.k1)
+    }
+
+    #[inline]
+    pub fn digest(&self, bytes: &[u8], wide: bool) -> SipOutput {
+        if wide {
+            let digest = SipHash128Function::new_with_keys(self.k0, self.k1).digest(bytes);
+            SipOutput::U128(digest.to_le_bytes())
+        } else {
+            SipOutput::U64(SipHashFunction::new_with_keys(self.k0, self.k1).digest(bytes))
+        }
+    }
+}
+
+/// Hint the processor to load the cache line containing `bytes[offset]`.
+#[inline(always)]
+fn prefetch(bytes: &[u8], offset: usize) {
+    debug_assert!(offset < bytes.len());
+    #[cfg(target_arch = "x86_64")]
+    unsafe {
+        use core::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+        _mm_prefetch::<_MM_HINT_T0>(bytes.as_ptr().add(offset) as *const i8);
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    let _ = (bytes, offset);
+}
+
+/// Adapter exposing a `HashContext` with a 64-bit result (by default a
+/// `SipContext`) as a `std::hash::Hasher`, e.g. to use it in a
+/// `std::collections::HashMap`.
+///
+/// `Hasher::finish` takes `&self` and may be called several times, while
+/// `HashContext::finish` consumes the context: each call finalizes a copy
+/// of the running state, so it returns the digest of everything written
+/// so far and more bytes can still be written afterwards.
+#[derive(Clone)]
+pub struct StdHasher<C = SipContext>(C);
+
+impl<C: HashContext<Result = u64> + Clone> StdHasher<C> {
+    #[inline]
+    pub fn new(ctx: C) -> StdHasher<C> {
+        StdHasher(ctx)
+    }
+}
+
+impl Default for StdHasher {
+    #[inline]
+    fn default() -> StdHasher {
+        StdHasher(SipHashFunction::new().init())
+    }
+}
+
+impl<C: HashContext<Result = u64> + Clone> core::hash::Hasher for StdHasher<C> {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes)
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0.clone().finish()
+    }
+}
+
+/// `std::hash::BuildHasher` producing a `StdHasher` for each `init()` of
+/// the hash function `F`, for using any hash function of this crate in
+/// std collections:
+/// `HashMap::with_hasher(StdBuildHasher::new(SipHashFunction::new_with_keys(k0, k1)))`.
+#[derive(Clone, Default)]
+pub struct StdBuildHasher<F>(F);
+
+impl<F> StdBuildHasher<F>
+    where F: HashFunction, F::Context: HashContext<Result = u64> + Clone
+{
+    #[inline]
+    pub fn new(f: F) -> StdBuildHasher<F> {
+        StdBuildHasher(f)
+    }
+}
+
+impl<F> core::hash::BuildHasher for StdBuildHasher<F>
+    where F: HashFunction, F::Context: HashContext<Result = u64> + Clone
+{
+    type Hasher = StdHasher<F::Context>;
+
+    #[inline]
+    fn build_hasher(&self) -> StdHasher<F::Context> {
+        StdHasher::new(self.0.init())
+    }
+}
+
+/// `std::hash::BuildHasher` producing `StdHasher`s, for using SipHash in
+/// std collections.
+///
+/// `new()` draws fresh keys for DoS resistance, like std's `RandomState`:
+/// each thread seeds its keys once from the OS randomness of
+/// `RandomState` and then increments them for every new build hasher.
+/// `with_keys()` gives deterministic hashing, e.g. for tests.
+#[derive(Clone)]
+pub struct SipBuildHasher {
+    k0: u64,
+    k1: u64,
+}
+
+impl SipBuildHasher {
+    #[cfg(feature = "std")]
+    pub fn new() -> SipBuildHasher {
+        thread_local!(static KEYS: Cell<(u64, u64)> = Cell::new(random_keys()));
+
+        KEYS.with(|keys| {
+            let (k0, k1) = keys.get();
+            keys.set((k0.wrapping_add(1), k1));
+            SipBuildHasher::with_keys(k0, k1)
+        })
+    }
+
+    #[inline]
+    pub fn with_keys(k0: u64, k1: u64) -> SipBuildHasher {
+        SipBuildHasher { k0, k1 }
+    }
+
+    #[inline]
+    pub fn with_key(key: &Key) -> SipBuildHasher {
+        SipBuildHasher::with_keys(key.k0, key.k1)
+    }
+}
+
+#[cfg(feature = "std")]
+fn random_keys() -> (u64, u64) {
+    use std::hash::{BuildHasher, Hasher};
+
+    let mut hasher = RandomState::new().build_hasher();
+    Hasher::write_u8(&mut hasher, 0);
+    let k0 = Hasher::finish(&hasher);
+    Hasher::write_u8(&mut hasher, 1);
+    (k0, Hasher::finish(&hasher))
+}
+
+#[cfg(feature = "std")]
+impl Default for SipBuildHasher {
+    #[inline]
+    fn default() -> SipBuildHasher {
+        SipBuildHasher::new()
+    }
+}
+
+impl core::hash::BuildHasher for SipBuildHasher {
+    type Hasher = StdHasher;
+
+    #[inline]
+    fn build_hasher(&self) -> StdHasher {
+        StdHasher::new(SipHashFunction::new_with_keys(self.k0, self.k1).init())
+    }
+}
+
+impl SipHashFunction {
+    /// Keyed digest of `bytes` tagged with a per-message `nonce`.
+    ///
+    /// The nonce is absorbed right after the keyed initialization, so
+    /// identical payloads produce different tags. Nonces must be unique
+    /// per message: reusing one makes the tags of equal payloads equal
+    /// again, and verification needs the same nonce used for tagging.
+    #[inline]
+    pub fn digest_nonced(&self, nonce: u64, bytes: &[u8]) -> u64 {
+        let mut ctx = self.init();
+        ctx.update(&nonce.to_le_bytes());
+        ctx.update(bytes);
+        ctx.finish()
+    }
+
+    /// Digest of `bytes` in the domain `label` of one of the helpers of
+    /// this crate, e.g. the `index`-th of several domain-separated
+    /// digests.
+    ///
+    /// The length-framed label and the index are absorbed first, so the
+    /// digests of two helpers never collide, even on the same index.
+    #[inline]
+    pub(crate) fn digest_domain(&self, label: &str, index: u64, bytes: &[u8]) -> u64 {
+        let mut ctx = self.init_domain(label, index);
+        ctx.update(bytes);
+        ctx.finish()
+    }
+
+    /// Context that has absorbed the domain of `digest_domain()`, for
+    /// helpers that feed more than a byte string.
+    #[inline]
+    pub(crate) fn init_domain(&self, label: &str, index: u64) -> SipContext {
+        let mut ctx = self.init();
+        label.hash(&mut ctx);
+        ctx.write_u64(index);
+        ctx
+    }
+
+    /// Digest of the concatenation `head || tail`, e.g. the two
+    /// contiguous segments of a ring buffer, without copying them into a
+    /// linear buffer first.
+    #[inline]
+    pub fn digest_wrapped(&self, head: &[u8], tail: &[u8]) -> u64 {
+        let mut ctx = self.init();
+        ctx.update(head);
+        ctx.update(tail);
+        ctx.finish()
+    }
+
+    /// Fast approximate fingerprint: hashes every `stride`-th byte
+    /// followed by the total length of `bytes`.
+    ///
+    /// This is *not* collision resistant: inputs that only differ in
+    /// bytes which are skipped by the sampling get the same fingerprint.
+    /// Panics if `stride` is 0.
+    pub fn digest_sampled(&self, bytes: &[u8], stride: usize) -> u64 {
+        assert!(stride > 0, "stride must be positive");
+        let mut ctx = self.init();
+        let mut buf = [0u8; 64];
+        let mut n = 0;
+        for &b in bytes.iter().step_by(stride) {
+            buf[n] = b;
+            n += 1;
+            if n == buf.len() {
+                ctx.update(&buf);
+                n = 0;
+            }
+        }
+        ctx.update(&buf[..n]);
+        ctx.update(&(bytes.len() as u64).to_le_bytes());
+        ctx.finish()
+    }
+
+    /// Same as `digest`, but issues software prefetch hints a few cache
+    /// lines ahead of the data being compressed, for large buffers that
+    /// are not already in cache. The hints are only emitted on x86_64.
+    pub fn digest_prefetch(&self, bytes: &[u8]) -> u64 {
+        const CHUNK: usize = 1024;
+        const AHEAD: usize = 4096;
+        const LINE: usize = 64;
+
+        let mut ctx = self.init();
+        for (i, chunk) in bytes.chunks(CHUNK).enumerate() {
+            let ahead = i * CHUNK + AHEAD;
+            for offset in (ahead..ahead + CHUNK).step_by(LINE).take_while(|&o| o < bytes.len()) {
+                prefetch(bytes, offset);
+            }
+            ctx.update(chunk);
+        }
+        ctx.finish()
+    }
+
+    /// Digest of each `block_size` block of `bytes`, the last one
+    /// possibly shorter, so that identical blocks can be deduplicated.
+    /// Panics if `block_size` is 0.
+    #[cfg(feature = "std")]
+    pub fn digest_blocks(&self, bytes: &[u8], block_size: usize) -> Vec<u64> {
+        bytes.chunks(block_size).map(|block| self.digest(block)).collect()
+    }
+
+    /// Digest of the byte-wise XOR of two equal-length buffers, or `None`
+    /// if their lengths differ.
+    ///
+    /// Identical buffers give the digest of all zeros, so this works as
+    /// a change fingerprint between two versions of the same data.
+    pub fn digest_xor(&self, a: &[u8], b: &[u8]) -> Option<u64> {
+        if a.len() != b.len() {
+            return None;
+        }
+
+        let mut ctx = self.init();
+        let mut buf = [0u8; 64];
+        for (ca, cb) in a.chunks(buf.len()).zip(b.chunks(buf.len())) {
+            for ((d, x), y) in buf.iter_mut().zip(ca).zip(cb) {
+                *d = x ^ y;
+            }
+            ctx.update(&buf[..ca.len()]);
+        }
+        Some(ctx.finish())
+    }
+
+    /// Digest of `id` salted with the time window containing
+    /// `epoch_secs`, so that identifiers are stable within a window of
+    /// `window_secs` seconds and unlinkable across windows.
+    /// Panics if `window_secs` is 0.
+    #[inline]
+    pub fn digest_time_bucketed(&self, id: &[u8], epoch_secs: u64, window_secs: u64) -> u64 {
+        self.digest_domain("hash::time_bucketed", epoch_secs / window_secs, id)
+    }
+
+    /// The smaller of the digests of `bytes` and of its reversal, so that
+    /// a buffer and its reverse get the same result.
+    pub fn digest_canonical_reversible(&self, bytes: &[u8]) -> u64 {
+        let forward = self.digest(bytes);
+
+        let mut ctx = self.init();
+        let mut buf = [0u8; 64];
+        for chunk in bytes.rchunks(buf.len()) {
+            for (d, &b) in buf.iter_mut().zip(chunk.iter().rev()) {
+                *d = b;
+            }
+            ctx.update(&buf[..chunk.len()]);
+        }
+        let reversed = ctx.finish();
+
+        core::cmp::min(forward, reversed)
+    }
+
+    /// Digest of `bytes` read as a sequence of 8-byte words in the given
+    /// byte order, the trailing partial word (if any) being hashed as is.
+    ///
+    /// For formats that record their endianness with a marker: with
+    /// `big_endian` set every word is byte-swapped before absorption, so
+    /// the same `u64` fields give the same digest whichever byte order
+    /// the file was written in.
+    pub fn digest_with_endianness(&self, bytes: &[u8], big_endian: bool) -> u64 {
+        if !big_endian {
+            return self.digest(bytes);
+        }
+
+        let mut ctx = self.init();
+        let mut buf = [0u8; 64];
+        for chunk in bytes.chunks(buf.len()) {
+            let words = chunk.len() & !7;
+            for (d, s) in buf.chunks_exact_mut(8).zip(chunk.chunks_exact(8)) {
+                for (x, &y) in d.iter_mut().zip(s.iter().rev()) {
+                    *x = y;
+                }
+            }
+            buf[words..chunk.len()].copy_from_slice(&chunk[words..]);
+            ctx.update(&buf[..chunk.len()]);
+        }
+        ctx.finish()
+    }
+
+    /// Order-independent digest of the multiset of `items`: the distinct
+    /// values are hashed in sorted order, each followed by its number
+    /// of occurrences as a u64, so `[a, a, b]` and `[a, b, b]` differ.
+    #[cfg(feature = "std")]
+    pub fn digest_counted<T: Hash + Ord + Clone>(&self, items: &[T]) -> u64 {
+        let mut sorted = items.to_vec();
+        sorted.sort_unstable();
+
+        let mut ctx = self.init();
+        for group in sorted.chunk_by(|a, b| a == b) {
+            group[0].hash(&mut ctx);
+            (group.len() as u64).hash(&mut ctx);
+        }
+        ctx.finish()
+    }
+
+    /// Digest of a sparse vector given as `(index, value)` pairs.
+    ///
+    /// Pairs are sorted by index and zero values (either sign) are
+    /// skipped, so the digest only depends on the logical vector, not on
+    /// the order of the pairs or on explicit zeros. All NaNs are hashed
+    /// as the same canonical NaN.
+    #[cfg(feature = "std")]
+    pub fn digest_sparse(&self, pairs: &[(u32, f64)]) -> u64 {
+        let mut entries: Vec<(u32, u64)> = pairs.iter()
+            .filter(|&&(_, value)| value != 0.0)
+            .map(|&(index, value)| (index, crate::traits::canonical_f64_bits(value)))
+            .collect();
+        entries.sort_unstable();
+
+        let mut ctx = self.init();
+        for (index, bits) in entries {
+            index.hash(&mut ctx);
+            bits.hash(&mut ctx);
+        }
+        ctx.finish()
+    }
+
+    /// Reproducible UUID built from two domain-separated digests of
+    /// `seed`, with the version (4) and variant (RFC 4122) bits set.
+    ///
+    /// Useful for stable IDs in tests and fixtures; the result is not
+    /// random and must not be used where unpredictability matters.
+    pub fn deterministic_uuid(&self, seed: &[u8]) -> [u8; 16] {
+        let mut uuid = [0u8; 16];
+        uuid[..8].copy_from_slice(&self.digest_domain("hash::uuid", 0, seed).to_le_bytes());
+        uuid[8..].copy_from_slice(&self.digest_domain("hash::uuid", 1, seed).to_le_bytes());
+        uuid[6] = (uuid[6] & 0x0f) | 0x40;
+        uuid[8] = (uuid[8] & 0x3f) | 0x80;
+        uuid
+    }
+
+    /// Expand `master` into `num_keys` subkeys, the `i`-th being the
+    /// digest of `master || i`, similarly to HKDF-expand.
+    ///
+    /// Requesting more keys extends the previous result. This is meant
+    /// for deriving keys from high-entropy material: it is fast on
+    /// purpose and is *not* a password KDF.
+    #[cfg(feature = "std")]
+    pub fn expand(&self, master: &[u8], num_keys: usize) -> Vec<u64> {
+        (0..num_keys as u64).map(|i| {
+            let mut ctx = self.init();
+            ctx.update(master);
+            ctx.update(&i.to_le_bytes());
+            ctx.finish()
+        }).collect()
+    }
+}
+
+#[cfg(feature = "unicode")]
+impl SipHashFunction {
+    /// Digest of the NFC normalization of `s`, so that canonically
+    /// equivalent strings (e.g. composed and decomposed accents) hash
+    /// equally.
+    pub fn digest_nfc(&self, s: &str) -> u64 {
+        use unicode_normalization::UnicodeNormalization;
+
+        let mut ctx = self.init();
+        let mut buf = [0u8; 64];
+        let mut n = 0;
+        for c in s.nfc() {
+            if n + c.len_utf8() > buf.len() {
+                ctx.update(&buf[..n]);
+                n = 0;
+            }
+            n += c.encode_utf8(&mut buf[n..]).len();
+        }
+        ctx.update(&buf[..n]);
+        ctx.finish()
+    }
+}
+
+/// Types that know how to turn themselves into a stable SipHash key.
+///
+/// This is implemented for every `Hash` type, giving domain types a
+/// named, discoverable method equivalent to `self.digest(f)`.
+pub trait HashKey {
+    fn hash_key(&self, f: &SipHashFunction) -> u64;
+}
+
+impl<T: Hash + ?Sized> HashKey for T {
+    #[inline]
+    fn hash_key(&self, f: &SipHashFunction) -> u64 {
+        self.digest(f)
+    }
 }
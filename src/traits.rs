@@ -5,8 +5,158 @@ pub trait HashContext {
 
     // This signature prevents from:
     //  - incorrect usage of `update()` after `finish()` :D
-    //  - exposing `reset()` :(
+    //  - exposing `reset()` :( (see `ResettableContext`)
     fn finish(self) -> Self::Result;
+
+    // These functions feed an integer as its little-endian bytes. They
+    // are equivalent to `update(&value.to_le_bytes())`, but contexts can
+    // override them with a faster path that skips the byte slice.
+    #[inline(always)]
+    fn write_u8(&mut self, value: u8) {
+        self.update(&[value])
+    }
+
+    #[inline(always)]
+    fn write_u16(&mut self, value: u16) {
+        self.update(&value.to_le_bytes())
+    }
+
+    #[inline(always)]
+    fn write_u32(&mut self, value: u32) {
+        self.update(&value.to_le_bytes())
+    }
+
+    #[inline(always)]
+    fn write_u64(&mut self, value: u64) {
+        self.update(&value.to_le_bytes())
+    }
+
+    #[inline(always)]
+    fn write_u128(&mut self, value: u128) {
+        self.write_u64(value as u64);
+        self.write_u64((value >> 64) as u64)
+    }
+
+    // `usize` is written at a fixed 64-bit width, so that 32-bit and
+    // 64-bit targets agree
+    #[inline(always)]
+    fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64)
+    }
+
+    // These functions feed a u64 in an explicit byte order, for
+    // matching formats that are not little-endian (`update_u64_le()` is
+    // the same as `write_u64()`)
+    #[inline(always)]
+    fn update_u64_le(&mut self, value: u64) {
+        self.write_u64(value)
+    }
+
+    #[inline(always)]
+    fn update_u64_be(&mut self, value: u64) {
+        self.update(&value.to_be_bytes())
+    }
+
+    // This function feeds the delta-encoded input, i.e. each byte
+    // minus the previous one (starting from `prev`). It returns the last
+    // byte of the input, which can be passed as `prev` to chain deltas
+    // across calls.
+    fn update_delta(&mut self, bytes: &[u8], prev: u8) -> u8 {
+        let mut buf = [0u8; 64];
+        let mut prev = prev;
+        for chunk in bytes.chunks(buf.len()) {
+            for (d, &b) in buf.iter_mut().zip(chunk) {
+                *d = b.wrapping_sub(prev);
+                prev = b;
+            }
+            self.update(&buf[..chunk.len()]);
+        }
+        prev
+    }
+
+    // This function feeds the LEB128 (protobuf varint) encoding of
+    // `value`, so that hashes match systems hashing the wire form.
+    fn update_varint(&mut self, value: u64) {
+        let mut buf = [0u8; 10];
+        let mut value = value;
+        let mut n = 0;
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                buf[n] = byte;
+                n += 1;
+                break;
+            }
+            buf[n] = byte | 0x80;
+            n += 1;
+        }
+        self.update(&buf[..n]);
+    }
+
+    // This function frames a variable-length value: it feeds `len` as a
+    // u64, like the length prefix of slices and strings. Collections
+    // that are not slices call it before hashing their elements.
+    #[inline(always)]
+    fn write_len_prefix(&mut self, len: usize) {
+        self.write_u64(len as u64)
+    }
+
+    // This function feeds a variant of a tagged union: the tag, then the
+    // length-framed payload, so that the same payload under two
+    // different tags never collides. Derived `Hash` impls for enums
+    // deliberately do not use it: framing the payload needs its length
+    // up front, i.e. buffering the fields or hashing them twice, and
+    // their fields would no longer be fed (and depth-limited) through
+    // the context itself. They feed the variant index as a single byte,
+    // followed by the fields of the variant
+    fn update_variant(&mut self, variant_tag: u64, payload: &[u8]) {
+        self.update(&variant_tag.to_le_bytes());
+        self.update(&(payload.len() as u64).to_le_bytes());
+        self.update(payload);
+    }
+
+    // This function feeds the DER encoding of a length: a single byte
+    // below 128 (short form), otherwise 0x80 | n followed by the n
+    // big-endian bytes of the length (long form)
+    fn update_der_length(&mut self, len: usize) {
+        if len < 0x80 {
+            self.update(&[len as u8]);
+            return;
+        }
+
+        let bytes = (len as u64).to_be_bytes();
+        let n = 8 - (len as u64).leading_zeros() as usize / 8;
+        let mut buf = [0u8; 9];
+        buf[0] = 0x80 | n as u8;
+        buf[1..=n].copy_from_slice(&bytes[8 - n..]);
+        self.update(&buf[..=n]);
+    }
+
+    // These functions bracket the hashing of a nested value (a derived
+    // type or a smart pointer), for contexts that bound the recursion
+    // depth such as `DepthLimited`. The contents are only hashed if
+    // `enter_nested()` returns true, and `leave_nested()` is called in
+    // either case. By default no depth is tracked and nothing is fed.
+    #[inline(always)]
+    fn enter_nested(&mut self) -> bool {
+        true
+    }
+
+    #[inline(always)]
+    fn leave_nested(&mut self) {}
+}
+
+/// A context that can be reused for several digests.
+///
+/// `finish_reset()` returns the digest of the bytes fed so far and resets
+/// the context to its freshly initialized state, which lets hot loops
+/// reuse a context instead of calling `init()` for every input (see
+/// `HashFunction::hasher_pool()`). `finish()` keeps consuming the
+/// context, so contexts that cannot reset themselves only implement
+/// `HashContext`.
+pub trait ResettableContext: HashContext {
+    fn finish_reset(&mut self) -> Self::Result;
 }
 
 pub trait HashFunction {
@@ -24,11 +174,335 @@ pub trait HashFunction {
         ctx.update(bytes);
         ctx.finish()
     }
+
+    // This function is the one-shot digest of `bytes` after their length
+    // prefix (see `HashContext::write_len_prefix()`), i.e. the digest of
+    // a byte slice or string as a `Hash` value. Hash functions with a
+    // one-shot `digest()` can override it the same way
+    #[inline(always)]
+    fn digest_prefixed(&self, bytes: &[u8]) -> <Self::Context as HashContext>::Result {
+        let mut ctx = self.init();
+        ctx.write_len_prefix(bytes.len());
+        ctx.update(bytes);
+        ctx.finish()
+    }
+
+    // This function returns a single context meant to be reused for
+    // many digests, without paying for `init()` every time. The context
+    // must implement `ResettableContext`.
+    #[inline]
+    fn hasher_pool(&self) -> HasherPool<Self::Context>
+        where Self::Context: ResettableContext
+    {
+        HasherPool { ctx: self.init() }
+    }
+
+    // This function is `Hash::digest()` with a `DepthLimited` context:
+    // instead of recursing without bound into deeply nested (or cyclic)
+    // values, it fails if they are nested more than `max_depth` levels
+    fn digest_bounded<T: Hash + ?Sized>(&self, value: &T, max_depth: usize) -> Result<<Self::Context as HashContext>::Result, DepthLimitExceeded>
+        where Self: Sized
+    {
+        let mut ctx = DepthLimited::new(self.init(), max_depth);
+        value.hash(&mut ctx);
+        ctx.finish()
+    }
+
+    // This function hashes the items of `iter` in order without
+    // collecting them, followed by their count as a u64
+    fn digest_iter<I>(&self, iter: I) -> <Self::Context as HashContext>::Result
+        where I: IntoIterator, I::Item: Hash
+    {
+        let mut ctx = self.init();
+        let mut count = 0u64;
+        for item in iter {
+            item.hash(&mut ctx);
+            count += 1;
+        }
+        ctx.write_u64(count);
+        ctx.finish()
+    }
+
+    // This function zero-pads the input up to the next multiple of
+    // `block` before hashing, then frames it with the original length
+    // so that the padding cannot be confused with actual zero bytes.
+    // Panics if `block` is 0.
+    fn digest_padded(&self, bytes: &[u8], block: usize) -> <Self::Context as HashContext>::Result {
+        const ZEROS: [u8; 64] = [0; 64];
+
+        let mut ctx = self.init();
+        ctx.update(bytes);
+
+        let mut pad = (block - bytes.len() % block) % block;
+        while pad > 0 {
+            let n = core::cmp::min(pad, ZEROS.len());
+            ctx.update(&ZEROS[..n]);
+            pad -= n;
+        }
+
+        ctx.update(&(bytes.len() as u64).to_le_bytes());
+        ctx.finish()
+    }
+
+    // This function absorbs a schema version before the payload, so
+    // that bumping the version invalidates every key derived from it
+    fn digest_versioned(&self, schema_version: u32, bytes: &[u8]) -> <Self::Context as HashContext>::Result {
+        let mut ctx = self.init();
+        ctx.update(&schema_version.to_le_bytes());
+        ctx.update(bytes);
+        ctx.finish()
+    }
+
+    // This function returns the length of the input along with its
+    // digest, to be used as a compound key by content-addressed stores:
+    // inputs of different lengths never share a key, even if their
+    // digests collide
+    #[inline]
+    fn content_id(&self, bytes: &[u8]) -> (u64, <Self::Context as HashContext>::Result) {
+        (bytes.len() as u64, self.digest(bytes))
+    }
+
+    // This function is `Hash::digest()` restricted to `PortableHash`
+    // values, for digests that are persisted or shared across machines
+    #[inline]
+    fn digest_portable<T: PortableHash + ?Sized>(&self, value: &T) -> <Self::Context as HashContext>::Result
+        where Self: Sized
+    {
+        value.digest(self)
+    }
+
+    // This function maps `item` onto one of `n` buckets, see
+    // `util::fast_range()`. For byte keys, `SipHashFunction` also has
+    // `bucket_bytes()`, which does not feed a length prefix.
+    // Panics if `n` is 0.
+    #[inline]
+    fn bucket<T: Hash + ?Sized>(&self, item: &T, n: usize) -> usize
+        where Self: Sized, Self::Context: HashContext<Result = u64>
+    {
+        assert!(n > 0, "n must be positive");
+        crate::util::fast_range(item.digest(self), n as u64) as usize
+    }
+
+    // This function spells the digest as (at most 8) words from the PGP
+    // word list, most significant byte first, for humans comparing
+    // fingerprints
+    #[cfg(feature = "std")]
+    fn digest_words(&self, bytes: &[u8], num_words: usize) -> Vec<&'static str>
+        where Self::Context: HashContext<Result = u64>
+    {
+        let digest = self.digest(bytes).to_be_bytes();
+        digest.iter().take(num_words).enumerate().map(|(i, &b)| {
+            if i % 2 == 0 { crate::words::EVEN[b as usize] } else { crate::words::ODD[b as usize] }
+        }).collect()
+    }
+
+    // This function memory-maps the file at `path`, hashes each
+    // `chunk`-sized piece of it and combines the piece digests in order,
+    // framed with the file length and the chunk size. Panics if `chunk`
+    // is 0.
+    #[cfg(feature = "mmap")]
+    fn digest_file<P: AsRef<std::path::Path>>(&self, path: P, chunk: usize) -> std::io::Result<u64>
+        where Self::Context: HashContext<Result = u64>
+    {
+        assert!(chunk > 0, "chunk must be positive");
+        crate::file::with_mapped(path, |bytes| {
+            let leaves: Vec<u64> = bytes.chunks(chunk).map(|c| self.digest(c)).collect();
+            crate::file::combine(self, bytes.len(), chunk, &leaves)
+        })
+    }
+
+    // This function computes the same digest as `digest_file()`, but it
+    // hashes the pieces across the rayon thread pool
+    #[cfg(all(feature = "mmap", feature = "parallel"))]
+    fn digest_file_parallel<P: AsRef<std::path::Path>>(&self, path: P, chunk: usize) -> std::io::Result<u64>
+        where Self: Sync, Self::Context: HashContext<Result = u64>
+    {
+        use rayon::prelude::*;
+
+        assert!(chunk > 0, "chunk must be positive");
+        crate::file::with_mapped(path, |bytes| {
+            let leaves: Vec<u64> = bytes.par_chunks(chunk).map(|c| self.digest(c)).collect();
+            crate::file::combine(self, bytes.len(), chunk, &leaves)
+        })
+    }
+
+    // This function hashes the input in bounded chunks, yielding to
+    // the tokio scheduler between them so that hashing a huge buffer
+    // does not starve the other tasks of the executor.
+    #[cfg(feature = "tokio")]
+    fn digest_async<'a>(&'a self, bytes: &'a [u8]) -> impl core::future::Future<Output = <Self::Context as HashContext>::Result> + 'a {
+        const CHUNK: usize = 64 * 1024;
+
+        async move {
+            let mut ctx = self.init();
+            let mut chunks = bytes.chunks(CHUNK);
+            if let Some(first) = chunks.next() {
+                ctx.update(first);
+            }
+            for chunk in chunks {
+                tokio::task::yield_now().await;
+                ctx.update(chunk);
+            }
+            ctx.finish()
+        }
+    }
+}
+
+/// A context reused across digests through `finish_reset()`, see
+/// `HashFunction::hasher_pool()`.
+///
+/// Each digest starts from the freshly initialized state, so the results
+/// are the same as with `HashFunction::digest()` and `Hash::digest()`.
+#[derive(Clone)]
+pub struct HasherPool<C> {
+    ctx: C,
 }
 
+impl<C: ResettableContext> HasherPool<C> {
+    #[inline]
+    pub fn digest(&mut self, bytes: &[u8]) -> C::Result {
+        self.ctx.update(bytes);
+        self.ctx.finish_reset()
+    }
+
+    #[inline]
+    pub fn digest_value<T: Hash + ?Sized>(&mut self, value: &T) -> C::Result {
+        value.hash(&mut self.ctx);
+        self.ctx.finish_reset()
+    }
+}
+
+/// Error of a `DepthLimited` context fed a value nested too deeply.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DepthLimitExceeded;
+
+impl core::fmt::Display for DepthLimitExceeded {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("value nested beyond the depth limit")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DepthLimitExceeded {}
+
+/// Context bounding the nesting depth of the values hashed into it.
+///
+/// Each derived value and each `Box`, `Rc` or `Arc` is one level of
+/// nesting. The contents of values nested deeper than `max_depth` levels
+/// are skipped rather than recursed into, so the stack stays bounded
+/// even for cyclic or very deep structures, and `finish()` returns an
+/// error. Values within the limit get the digest of the inner context.
+#[derive(Clone)]
+pub struct DepthLimited<C> {
+    ctx: C,
+    depth: usize,
+    max_depth: usize,
+    exceeded: bool,
+}
+
+impl<C: HashContext> DepthLimited<C> {
+    #[inline]
+    pub fn new(ctx: C, max_depth: usize) -> DepthLimited<C> {
+        DepthLimited { ctx, depth: 0, max_depth, exceeded: false }
+    }
+}
+
+impl<C: HashContext> HashContext for DepthLimited<C> {
+    type Result = Result<C::Result, DepthLimitExceeded>;
+
+    #[inline(always)]
+    fn update(&mut self, bytes: &[u8]) {
+        self.ctx.update(bytes)
+    }
+
+    #[inline]
+    fn finish(self) -> Result<C::Result, DepthLimitExceeded> {
+        if self.exceeded {
+            Err(DepthLimitExceeded)
+        } else {
+            Ok(self.ctx.finish())
+        }
+    }
+
+    #[inline(always)]
+    fn write_u8(&mut self, value: u8) {
+        self.ctx.write_u8(value)
+    }
+
+    #[inline(always)]
+    fn write_u16(&mut self, value: u16) {
+        self.ctx.write_u16(value)
+    }
+
+    #[inline(always)]
+    fn write_u32(&mut self, value: u32) {
+        self.ctx.write_u32(value)
+    }
+
+    #[inline(always)]
+    fn write_u64(&mut self, value: u64) {
+        self.ctx.write_u64(value)
+    }
+
+    #[inline]
+    fn enter_nested(&mut self) -> bool {
+        let inner = self.ctx.enter_nested();
+        self.depth += 1;
+        if self.depth > self.max_depth {
+            self.exceeded = true;
+        }
+        inner && !self.exceeded
+    }
+
+    #[inline]
+    fn leave_nested(&mut self) {
+        self.depth -= 1;
+        self.ctx.leave_nested()
+    }
+}
+
+/// A value that can be fed to a `HashContext`.
+///
+/// The encodings of the provided impls are prefix-free within a type:
+/// variable-length values (slices, strings, maps) are prefixed with their
+/// length and enum-like values (`Option`, `Result`, derived enums) with
+/// their discriminant, so that composite keys such as `("ab", "c")` and
+/// `("a", "bc")` feed different bytes to the context. Fixed-size values
+/// (integers, arrays, tuples) need no prefix.
 pub trait Hash {
     fn hash<H: HashContext>(&self, ctx: &mut H);
 
+    // This function hashes the elements of a slice, without any length
+    // prefix. The default hashes them one by one; types can override it
+    // to feed the whole slice at once.
+    #[inline]
+    fn hash_slice<H: HashContext>(data: &[Self], ctx: &mut H) where Self: Sized {
+        for piece in data {
+            piece.hash(ctx)
+        }
+    }
+
+    // This function is the digest of the elements of a slice, without any
+    // length prefix. Types can override it to use the one-shot
+    // `HashFunction::digest` of the hash function.
+    #[inline]
+    fn digest_slice<H: HashFunction>(data: &[Self], f: &H) -> <<H as HashFunction>::Context as HashContext>::Result where Self: Sized {
+        let mut ctx = f.init();
+        Self::hash_slice(data, &mut ctx);
+        ctx.finish()
+    }
+
+    // This function is the digest of a slice with its length prefix, i.e.
+    // `Hash::digest()` of `[Self]`. Types can override it to use the
+    // one-shot `HashFunction::digest_prefixed` of the hash function.
+    #[inline]
+    fn digest_prefixed_slice<H: HashFunction>(data: &[Self], f: &H) -> <<H as HashFunction>::Context as HashContext>::Result where Self: Sized {
+        let mut ctx = f.init();
+        ctx.write_len_prefix(data.len());
+        Self::hash_slice(data, &mut ctx);
+        ctx.finish()
+    }
+
     #[inline]
     fn digest<H: HashFunction>(&self, f: &H) -> <<H as HashFunction>::Context as HashContext>::Result {
         let mut ctx = f.init();
@@ -40,17 +514,476 @@ pub trait Hash {
 impl Hash for u8 {
     #[inline(always)]
     fn hash<H: HashContext>(&self, ctx: &mut H) {
-        ctx.update(&[*self])
+        ctx.write_u8(*self)
+    }
+
+    #[inline(always)]
+    fn hash_slice<H: HashContext>(data: &[u8], ctx: &mut H) {
+        ctx.update(data)
+    }
+
+    #[inline(always)]
+    fn digest_slice<H: HashFunction>(data: &[u8], f: &H) -> <<H as HashFunction>::Context as HashContext>::Result {
+        f.digest(data)
+    }
+
+    #[inline(always)]
+    fn digest_prefixed_slice<H: HashFunction>(data: &[u8], f: &H) -> <<H as HashFunction>::Context as HashContext>::Result {
+        f.digest_prefixed(data)
+    }
+}
+
+// Integers are hashed as their little-endian bytes, so that digests do
+// not depend on the endianness of the target
+macro_rules! impl_hash_int {
+    ($($t:ty => $write:ident($u:ty),)*) => ($(
+        impl Hash for $t {
+            #[inline(always)]
+            fn hash<H: HashContext>(&self, ctx: &mut H) {
+                ctx.$write(*self as $u)
+            }
+        }
+    )*)
+}
+
+// `usize` and `isize` are hashed at a fixed 64-bit width, so that 32-bit
+// and 64-bit targets agree
+impl_hash_int! {
+    u16 => write_u16(u16),
+    u32 => write_u32(u32),
+    u64 => write_u64(u64),
+    u128 => write_u128(u128),
+    usize => write_usize(usize),
+    i8 => write_u8(u8),
+    i16 => write_u16(u16),
+    i32 => write_u32(u32),
+    i64 => write_u64(u64),
+    i128 => write_u128(u128),
+    isize => write_usize(usize),
+}
+
+impl Hash for bool {
+    #[inline(always)]
+    fn hash<H: HashContext>(&self, ctx: &mut H) {
+        (*self as u8).hash(ctx)
+    }
+}
+
+// `char` is hashed as its scalar value, like a `u32`
+impl Hash for char {
+    #[inline(always)]
+    fn hash<H: HashContext>(&self, ctx: &mut H) {
+        (*self as u32).hash(ctx)
+    }
+}
+
+/// Bits of `value`, with `-0.0` mapped to `0.0` and every NaN to the
+/// same canonical NaN.
+#[inline]
+pub(crate) fn canonical_f64_bits(value: f64) -> u64 {
+    if value.is_nan() {
+        f64::NAN.to_bits()
+    } else if value == 0.0 {
+        0
+    } else {
+        value.to_bits()
+    }
+}
+
+/// Same as `canonical_f64_bits`, for `f32`.
+#[inline]
+pub(crate) fn canonical_f32_bits(value: f32) -> u32 {
+    if value.is_nan() {
+        f32::NAN.to_bits()
+    } else if value == 0.0 {
+        0
+    } else {
+        value.to_bits()
+    }
+}
+
+// Floats are hashed as the little-endian bytes of their bits, after
+// mapping `-0.0` to `0.0` (they compare equal, so they hash the same)
+// and every NaN to a single canonical NaN (so that NaNs produced by
+// different computations or platforms agree)
+impl Hash for f64 {
+    #[inline(always)]
+    fn hash<H: HashContext>(&self, ctx: &mut H) {
+        canonical_f64_bits(*self).hash(ctx)
     }
 }
 
-impl Hash for [u8] {
+impl Hash for f32 {
     #[inline(always)]
     fn hash<H: HashContext>(&self, ctx: &mut H) {
-        ctx.update(self)
+        canonical_f32_bits(*self).hash(ctx)
+    }
+}
+
+// Slices and strings are prefixed with their length (as a u64), so that
+// the encoding is prefix-free: `["ab", "c"]` and `["a", "bc"]` differ,
+// and so do `[]` and `[""]`. Values of different types can still share
+// an encoding, e.g. `""` and an empty slice.
+impl<T: Hash> Hash for [T] {
+    #[inline]
+    fn hash<H: HashContext>(&self, ctx: &mut H) {
+        ctx.write_len_prefix(self.len());
+        T::hash_slice(self, ctx)
+    }
+
+    #[inline]
+    fn digest<H: HashFunction>(&self, f: &H) -> <<H as HashFunction>::Context as HashContext>::Result {
+        T::digest_prefixed_slice(self, f)
+    }
+}
+
+impl Hash for str {
+    #[inline]
+    fn hash<H: HashContext>(&self, ctx: &mut H) {
+        self.as_bytes().hash(ctx)
+    }
+
+    #[inline]
+    fn digest<H: HashFunction>(&self, f: &H) -> <<H as HashFunction>::Context as HashContext>::Result {
+        self.as_bytes().digest(f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Hash for String {
+    #[inline]
+    fn hash<H: HashContext>(&self, ctx: &mut H) {
+        self.as_str().hash(ctx)
     }
 
+    #[inline]
     fn digest<H: HashFunction>(&self, f: &H) -> <<H as HashFunction>::Context as HashContext>::Result {
-        f.digest(self)
+        self.as_str().digest(f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Hash> Hash for Vec<T> {
+    #[inline]
+    fn hash<H: HashContext>(&self, ctx: &mut H) {
+        self.as_slice().hash(ctx)
+    }
+
+    #[inline]
+    fn digest<H: HashFunction>(&self, f: &H) -> <<H as HashFunction>::Context as HashContext>::Result {
+        self.as_slice().digest(f)
+    }
+}
+
+// The size of arrays and tuples is part of their type, so they are
+// hashed as their elements in order, with no length prefix: `(1u8, 2u8)`
+// and `[1u8, 2u8]` both hash like the bytes `[1, 2]`. Digesting a byte
+// array goes through the one-shot `HashFunction::digest`, while byte
+// slices and strings go through `HashFunction::digest_prefixed`.
+impl<T: Hash, const N: usize> Hash for [T; N] {
+    #[inline]
+    fn hash<H: HashContext>(&self, ctx: &mut H) {
+        T::hash_slice(self, ctx)
+    }
+
+    #[inline]
+    fn digest<H: HashFunction>(&self, f: &H) -> <<H as HashFunction>::Context as HashContext>::Result {
+        T::digest_slice(self, f)
+    }
+}
+
+macro_rules! impl_hash_tuple {
+    ($($name:ident)+) => (
+        impl<$($name: Hash),+> Hash for ($($name,)+) {
+            #[inline]
+            #[allow(non_snake_case)]
+            fn hash<H: HashContext>(&self, ctx: &mut H) {
+                let ($(ref $name,)+) = *self;
+                $($name.hash(ctx);)+
+            }
+        }
+
+        impl<$($name: PortableHash),+> PortableHash for ($($name,)+) {}
+    )
+}
+
+impl_hash_tuple! { T0 }
+impl_hash_tuple! { T0 T1 }
+impl_hash_tuple! { T0 T1 T2 }
+impl_hash_tuple! { T0 T1 T2 T3 }
+impl_hash_tuple! { T0 T1 T2 T3 T4 }
+impl_hash_tuple! { T0 T1 T2 T3 T4 T5 }
+impl_hash_tuple! { T0 T1 T2 T3 T4 T5 T6 }
+impl_hash_tuple! { T0 T1 T2 T3 T4 T5 T6 T7 }
+impl_hash_tuple! { T0 T1 T2 T3 T4 T5 T6 T7 T8 }
+impl_hash_tuple! { T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 }
+impl_hash_tuple! { T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 }
+impl_hash_tuple! { T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 }
+
+// `None` is hashed as the byte 0, `Some(value)` as the byte 1 followed
+// by `value`
+impl<T: Hash> Hash for Option<T> {
+    #[inline]
+    fn hash<H: HashContext>(&self, ctx: &mut H) {
+        match *self {
+            None => 0u8.hash(ctx),
+            Some(ref value) => {
+                1u8.hash(ctx);
+                value.hash(ctx)
+            }
+        }
+    }
+}
+
+// `Ok(value)` is hashed as the byte 0 followed by `value`, `Err(error)`
+// as the byte 1 followed by `error`
+impl<T: Hash, E: Hash> Hash for Result<T, E> {
+    #[inline]
+    fn hash<H: HashContext>(&self, ctx: &mut H) {
+        match *self {
+            Ok(ref value) => {
+                0u8.hash(ctx);
+                value.hash(ctx)
+            }
+            Err(ref error) => {
+                1u8.hash(ctx);
+                error.hash(ctx)
+            }
+        }
+    }
+}
+
+impl<T: Hash + ?Sized> Hash for &T {
+    #[inline(always)]
+    fn hash<H: HashContext>(&self, ctx: &mut H) {
+        (**self).hash(ctx)
     }
+
+    #[inline(always)]
+    fn digest<H: HashFunction>(&self, f: &H) -> <<H as HashFunction>::Context as HashContext>::Result {
+        (**self).digest(f)
+    }
+}
+
+impl<T: Hash + ?Sized> Hash for &mut T {
+    #[inline(always)]
+    fn hash<H: HashContext>(&self, ctx: &mut H) {
+        (**self).hash(ctx)
+    }
+
+    #[inline(always)]
+    fn digest<H: HashFunction>(&self, f: &H) -> <<H as HashFunction>::Context as HashContext>::Result {
+        (**self).digest(f)
+    }
+}
+
+// Smart pointers are hashed as the value they point to. They are how
+// values recurse, so they count as a level of nesting (see
+// `HashContext::enter_nested()`). Their digest is the digest of the
+// value, which keeps its one-shot path if it has one
+#[cfg(feature = "std")]
+impl<T: Hash + ?Sized> Hash for Box<T> {
+    #[inline(always)]
+    fn hash<H: HashContext>(&self, ctx: &mut H) {
+        if ctx.enter_nested() {
+            (**self).hash(ctx)
+        }
+        ctx.leave_nested()
+    }
+
+    #[inline(always)]
+    fn digest<H: HashFunction>(&self, f: &H) -> <<H as HashFunction>::Context as HashContext>::Result {
+        (**self).digest(f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Hash + ?Sized> Hash for std::rc::Rc<T> {
+    #[inline(always)]
+    fn hash<H: HashContext>(&self, ctx: &mut H) {
+        if ctx.enter_nested() {
+            (**self).hash(ctx)
+        }
+        ctx.leave_nested()
+    }
+
+    #[inline(always)]
+    fn digest<H: HashFunction>(&self, f: &H) -> <<H as HashFunction>::Context as HashContext>::Result {
+        (**self).digest(f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Hash + ?Sized> Hash for std::sync::Arc<T> {
+    #[inline(always)]
+    fn hash<H: HashContext>(&self, ctx: &mut H) {
+        if ctx.enter_nested() {
+            (**self).hash(ctx)
+        }
+        ctx.leave_nested()
+    }
+
+    #[inline(always)]
+    fn digest<H: HashFunction>(&self, f: &H) -> <<H as HashFunction>::Context as HashContext>::Result {
+        (**self).digest(f)
+    }
+}
+
+// Insertion order is semantic for an `IndexMap`, so it is hashed as its
+// length followed by the entries in order: the same entries inserted in
+// a different order give a different digest
+#[cfg(feature = "indexmap")]
+impl<K: Hash, V: Hash, S> Hash for indexmap::IndexMap<K, V, S> {
+    fn hash<H: HashContext>(&self, ctx: &mut H) {
+        ctx.write_len_prefix(self.len());
+        for (k, v) in self {
+            k.hash(ctx);
+            v.hash(ctx);
+        }
+    }
+}
+
+// Ordered collections are hashed as their length followed by their
+// entries in iteration (i.e. key) order
+#[cfg(feature = "std")]
+impl<K: Hash, V: Hash> Hash for std::collections::BTreeMap<K, V> {
+    fn hash<H: HashContext>(&self, ctx: &mut H) {
+        ctx.write_len_prefix(self.len());
+        for (k, v) in self {
+            k.hash(ctx);
+            v.hash(ctx);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Hash> Hash for std::collections::BTreeSet<T> {
+    fn hash<H: HashContext>(&self, ctx: &mut H) {
+        ctx.write_len_prefix(self.len());
+        for item in self {
+            item.hash(ctx);
+        }
+    }
+}
+
+/// Context recording the bytes it is fed.
+#[cfg(feature = "std")]
+struct Recorder(Vec<u8>);
+
+#[cfg(feature = "std")]
+impl HashContext for Recorder {
+    type Result = Vec<u8>;
+
+    #[inline]
+    fn update(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes)
+    }
+
+    #[inline]
+    fn finish(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+// The iteration order of hash-based collections depends on their
+// `BuildHasher`, so they are hashed as their length followed by the
+// encodings of their entries sorted bytewise: equal collections give
+// the same digest regardless of their hasher or insertion order. This
+// buffers the encoding of every entry.
+#[cfg(feature = "std")]
+fn hash_unordered<H: HashContext, I>(ctx: &mut H, entries: I)
+    where I: ExactSizeIterator, I::Item: Hash
+{
+    ctx.write_len_prefix(entries.len());
+    let mut encodings: Vec<Vec<u8>> = entries.map(|entry| {
+        let mut recorder = Recorder(Vec::new());
+        entry.hash(&mut recorder);
+        recorder.finish()
+    }).collect();
+    encodings.sort_unstable();
+    for encoding in encodings {
+        ctx.update(&encoding);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Hash, V: Hash, S> Hash for std::collections::HashMap<K, V, S> {
+    fn hash<H: HashContext>(&self, ctx: &mut H) {
+        hash_unordered(ctx, self.iter())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Hash, S> Hash for std::collections::HashSet<T, S> {
+    fn hash<H: HashContext>(&self, ctx: &mut H) {
+        hash_unordered(ctx, self.iter())
+    }
+}
+
+/// Marker for types whose `Hash` encoding is the same on every target,
+/// so that their digests can be persisted or exchanged between machines
+/// (given a portable hash function).
+///
+/// The provided impls only use fixed-width little-endian encodings:
+/// integers are written as their LE bytes, `usize`/`isize` (and lengths)
+/// as 64-bit values, floats as the LE bytes of their canonical bits and
+/// `char` as a `u32`; composite types are portable when their elements
+/// are. Implementing it for a type means promising the same, e.g. never
+/// hashing native-endian or pointer-sized data (see `digest_pod()`).
+pub trait PortableHash: Hash {}
+
+macro_rules! impl_portable_hash {
+    ($($t:ty)*) => ($(
+        impl PortableHash for $t {}
+    )*)
+}
+
+impl_portable_hash! { u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize bool char f32 f64 str }
+
+#[cfg(feature = "std")]
+impl_portable_hash! { String }
+
+impl<T: PortableHash> PortableHash for [T] {}
+impl<T: PortableHash, const N: usize> PortableHash for [T; N] {}
+impl<T: PortableHash> PortableHash for Option<T> {}
+impl<T: PortableHash, E: PortableHash> PortableHash for Result<T, E> {}
+impl<T: PortableHash + ?Sized> PortableHash for &T {}
+impl<T: PortableHash + ?Sized> PortableHash for &mut T {}
+
+#[cfg(feature = "std")]
+impl<T: PortableHash> PortableHash for Vec<T> {}
+#[cfg(feature = "std")]
+impl<T: PortableHash + ?Sized> PortableHash for Box<T> {}
+#[cfg(feature = "std")]
+impl<T: PortableHash + ?Sized> PortableHash for std::rc::Rc<T> {}
+#[cfg(feature = "std")]
+impl<T: PortableHash + ?Sized> PortableHash for std::sync::Arc<T> {}
+#[cfg(feature = "std")]
+impl<K: PortableHash, V: PortableHash> PortableHash for std::collections::BTreeMap<K, V> {}
+#[cfg(feature = "std")]
+impl<T: PortableHash> PortableHash for std::collections::BTreeSet<T> {}
+#[cfg(feature = "std")]
+impl<K: PortableHash, V: PortableHash, S> PortableHash for std::collections::HashMap<K, V, S> {}
+#[cfg(feature = "std")]
+impl<T: PortableHash, S> PortableHash for std::collections::HashSet<T, S> {}
+#[cfg(feature = "indexmap")]
+impl<K: PortableHash, V: PortableHash, S> PortableHash for indexmap::IndexMap<K, V, S> {}
+
+/// Digest of the in-memory representation of a plain-old-data value.
+///
+/// This is a fast path for fixed-layout (`#[repr(C)]`) structs, hashing
+/// `size_of::<T>()` bytes starting at `value`.
+///
+/// # Safety
+///
+/// Every byte of `T` must be initialized: **padding bytes are not**, so
+/// `T` must have no padding (reading it is undefined behaviour, and even
+/// in practice two equal values could hash differently). `T` should not
+/// contain pointers or references either, as the address rather than the
+/// pointee would be hashed. The result also depends on the endianness
+/// of the target.
+pub unsafe fn digest_pod<T: Copy, H: HashFunction>(value: &T, f: &H) -> <H::Context as HashContext>::Result {
+    let bytes = core::slice::from_raw_parts(value as *const T as *const u8,
+                                            core::mem::size_of::<T>());
+    f.digest(bytes)
 }
@@ -0,0 +1,127 @@
+use crate::traits::{Hash, HashContext, HashFunction, ResettableContext};
+
+type Digest<F> = <<F as HashFunction>::Context as HashContext>::Result;
+
+/// Tree hashing mode of a hash function, for large inputs.
+///
+/// The input is split into `leaf_size`-byte leaves (the last one may be
+/// shorter) which are digested independently; the result is the digest,
+/// with the same function, of the input length and leaf size followed by
+/// the leaf digests in order. Leaf boundaries only depend on the leaf
+/// size, so `digest_parallel()` gives the same result as `digest()` and
+/// streaming, whatever the number of threads.
+#[derive(Clone)]
+pub struct TreeHash<F> {
+    f: F,
+    leaf_size: usize,
+}
+
+impl<F: HashFunction> TreeHash<F> {
+    /// Panics if `leaf_size` is 0.
+    #[inline]
+    pub fn new(f: F, leaf_size: usize) -> TreeHash<F> {
+        assert!(leaf_size > 0, "leaf_size must be positive");
+        TreeHash { f, leaf_size }
+    }
+
+    fn combine(&self, len: usize, leaves: &[Digest<F>]) -> Digest<F>
+        where Digest<F>: Hash
+    {
+        let mut ctx = self.f.init();
+        ctx.write_u64(len as u64);
+        ctx.write_u64(self.leaf_size as u64);
+        for leaf in leaves {
+            leaf.hash(&mut ctx);
+        }
+        ctx.finish()
+    }
+
+    /// Same as `digest()`, but the leaves are hashed across the rayon
+    /// thread pool.
+    #[cfg(feature = "parallel")]
+    pub fn digest_parallel(&self, bytes: &[u8]) -> Digest<F>
+        where F: Sync, Digest<F>: Hash + Send
+    {
+        use rayon::prelude::*;
+
+        let leaves: Vec<_> = bytes.par_chunks(self.leaf_size).map(|c| self.f.digest(c)).collect();
+        self.combine(bytes.len(), &leaves)
+    }
+}
+
+pub struct TreeContext<F: HashFunction> {
+    tree: TreeHash<F>,
+    leaf: F::Context,
+    filled: usize, // how many bytes in the current leaf
+    length: usize, // how many bytes we've processed
+    leaves: Vec<Digest<F>>,
+}
+
+impl<F: HashFunction> HashContext for TreeContext<F>
+    where Digest<F>: Hash
+{
+    type Result = Digest<F>;
+
+    fn update(&mut self, msg: &[u8]) {
+        self.length += msg.len();
+
+        let mut msg = msg;
+        while !msg.is_empty() {
+            let n = core::cmp::min(self.tree.leaf_size - self.filled, msg.len());
+            self.leaf.update(&msg[..n]);
+            self.filled += n;
+            msg = &msg[n..];
+            if self.filled == self.tree.leaf_size {
+                let leaf = core::mem::replace(&mut self.leaf, self.tree.f.init());
+                self.leaves.push(leaf.finish());
+                self.filled = 0;
+            }
+        }
+    }
+
+    fn finish(self) -> Digest<F> {
+        let mut leaves = self.leaves;
+        if self.filled != 0 {
+            leaves.push(self.leaf.finish());
+        }
+        self.tree.combine(self.length, &leaves)
+    }
+}
+
+impl<F: HashFunction> ResettableContext for TreeContext<F>
+    where Digest<F>: Hash
+{
+    fn finish_reset(&mut self) -> Digest<F> {
+        if self.filled != 0 {
+            let leaf = core::mem::replace(&mut self.leaf, self.tree.f.init());
+            self.leaves.push(leaf.finish());
+        }
+        let result = self.tree.combine(self.length, &self.leaves);
+        self.leaves.clear();
+        self.filled = 0;
+        self.length = 0;
+        result
+    }
+}
+
+impl<F: HashFunction + Clone> HashFunction for TreeHash<F>
+    where Digest<F>: Hash
+{
+    type Context = TreeContext<F>;
+
+    #[inline]
+    fn init(&self) -> TreeContext<F> {
+        TreeContext {
+            tree: self.clone(),
+            leaf: self.f.init(),
+            filled: 0,
+            length: 0,
+            leaves: Vec::new(),
+        }
+    }
+
+    fn digest(&self, bytes: &[u8]) -> Digest<F> {
+        let leaves: Vec<_> = bytes.chunks(self.leaf_size).map(|c| self.f.digest(c)).collect();
+        self.combine(bytes.len(), &leaves)
+    }
+}
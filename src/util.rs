@@ -0,0 +1,63 @@
+use crate::sip::Key;
+use crate::traits::{Hash, HashContext, HashFunction};
+
+/// Map `hash` uniformly onto `[0, n)` with Lemire's multiply-shift
+/// ("fastrange") reduction.
+///
+/// Unlike `hash % n`, this uses the high bits of the hash, is much
+/// cheaper than a division and does not need `n` to be a power of two to
+/// avoid a noticeable bias.
+#[inline]
+pub fn fast_range(hash: u64, n: u64) -> u64 {
+    ((u128::from(hash) * u128::from(n)) >> 64) as u64
+}
+
+/// Short fingerprint of an item, as stored by cuckoo and quotient
+/// filters: the top `BITS` bits of its 64-bit digest.
+///
+/// Fingerprints are never zero (a zero digest prefix is mapped to 1), so
+/// that 0 can mark empty slots. `BITS` must be in `1..=64`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Fingerprint<const BITS: u32>(u64);
+
+impl<const BITS: u32> Fingerprint<BITS> {
+    #[inline]
+    pub fn from_hash(hash: u64) -> Fingerprint<BITS> {
+        assert!(BITS > 0 && BITS <= 64, "BITS must be in 1..=64");
+        let value = hash >> (64 - BITS);
+        Fingerprint(if value == 0 { 1 } else { value })
+    }
+
+    #[inline]
+    pub fn new<F, T>(f: &F, item: &T) -> Fingerprint<BITS>
+        where F: HashFunction, F::Context: HashContext<Result = u64>, T: Hash + ?Sized
+    {
+        Fingerprint::from_hash(item.digest(f))
+    }
+
+    #[inline]
+    pub fn value(self) -> u64 {
+        self.0
+    }
+}
+
+/// Key for an independent hasher derived from `f` and `tag`.
+///
+/// The key words are digests of `tag` under a fixed, length-framed label
+/// and the word index, so different tags give unrelated keys that do not
+/// collide with the digests of other helpers, and the derivation can be
+/// repeated on the derived function to split it further. The keys are as
+/// secret as `f` is.
+pub fn derive_seed<F>(f: &F, tag: u64) -> Key
+    where F: HashFunction, F::Context: HashContext<Result = u64>
+{
+    let mut words = [0u64; 2];
+    for (i, word) in words.iter_mut().enumerate() {
+        let mut ctx = f.init();
+        "hash::derive_seed".hash(&mut ctx);
+        ctx.write_u64(i as u64);
+        ctx.write_u64(tag);
+        *word = ctx.finish();
+    }
+    Key::new(words[0], words[1])
+}
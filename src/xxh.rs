@@ -0,0 +1,504 @@
+use core::convert::TryInto;
+
+use crate::traits::{HashContext, HashFunction, ResettableContext};
+
+const PRIME32_1: u64 = 0x9E3779B1;
+const PRIME32_2: u64 = 0x85EBCA77;
+const PRIME32_3: u64 = 0xC2B2AE3D;
+const PRIME64_1: u64 = 0x9E3779B185EBCA87;
+const PRIME64_2: u64 = 0xC2B2AE3D27D4EB4F;
+const PRIME64_3: u64 = 0x165667B19E3779F9;
+const PRIME64_4: u64 = 0x85EBCA77C2B2AE63;
+const PRIME64_5: u64 = 0x27D4EB2F165667C5;
+
+#[inline(always)]
+fn read_u64(buf: &[u8], i: usize) -> u64 {
+    u64::from_le_bytes(buf[i..i + 8].try_into().unwrap())
+}
+
+#[inline(always)]
+fn read_u32(buf: &[u8], i: usize) -> u32 {
+    u32::from_le_bytes(buf[i..i + 4].try_into().unwrap())
+}
+
+/// Final mix of XXH64, also used by some XXH3 paths.
+#[inline(always)]
+fn xxh64_avalanche(mut h: u64) -> u64 {
+    h ^= h >> 33;
+    h = h.wrapping_mul(PRIME64_2);
+    h ^= h >> 29;
+    h = h.wrapping_mul(PRIME64_3);
+    h ^ (h >> 32)
+}
+
+/// XXH64, with a 64-bit seed.
+#[derive(Clone, Copy, Default)]
+pub struct Xxh64HashFunction {
+    seed: u64,
+}
+
+impl Xxh64HashFunction {
+    #[inline]
+    pub fn new() -> Xxh64HashFunction {
+        Xxh64HashFunction::new_with_seed(0)
+    }
+
+    #[inline]
+    pub fn new_with_seed(seed: u64) -> Xxh64HashFunction {
+        Xxh64HashFunction { seed }
+    }
+}
+
+#[derive(Clone)]
+pub struct Xxh64Context {
+    seed: u64,
+    v: [u64; 4],
+    length: u64,      // how many bytes we've processed
+    buf: [u8; 32],    // unprocessed bytes
+    nbuf: usize,      // how many bytes in buf are valid
+}
+
+#[inline(always)]
+fn xxh64_round(acc: u64, input: u64) -> u64 {
+    acc.wrapping_add(input.wrapping_mul(PRIME64_2))
+        .rotate_left(31)
+        .wrapping_mul(PRIME64_1)
+}
+
+#[inline(always)]
+fn xxh64_merge(acc: u64, v: u64) -> u64 {
+    (acc ^ xxh64_round(0, v)).wrapping_mul(PRIME64_1).wrapping_add(PRIME64_4)
+}
+
+impl Xxh64Context {
+    #[inline(always)]
+    fn stripe(&mut self, stripe: &[u8]) {
+        for (i, v) in self.v.iter_mut().enumerate() {
+            *v = xxh64_round(*v, read_u64(stripe, 8 * i));
+        }
+    }
+}
+
+impl HashContext for Xxh64Context {
+    type Result = u64;
+
+    #[inline]
+    fn update(&mut self, msg: &[u8]) {
+        let mut msg = msg;
+        self.length += msg.len() as u64;
+
+        if self.nbuf != 0 {
+            let n = core::cmp::min(32 - self.nbuf, msg.len());
+            self.buf[self.nbuf..self.nbuf + n].copy_from_slice(&msg[..n]);
+            self.nbuf += n;
+            msg = &msg[n..];
+            if self.nbuf < 32 {
+                return
+            }
+            let buf = self.buf;
+            self.stripe(&buf);
+            self.nbuf = 0;
+        }
+
+        let mut stripes = msg.chunks_exact(32);
+        for stripe in &mut stripes {
+            self.stripe(stripe);
+        }
+        let rest = stripes.remainder();
+        self.buf[..rest.len()].copy_from_slice(rest);
+        self.nbuf = rest.len();
+    }
+
+    fn finish(self) -> u64 {
+        let mut h = if self.length >= 32 {
+            let [v1, v2, v3, v4] = self.v;
+            let mut h = v1.rotate_left(1)
+                .wrapping_add(v2.rotate_left(7))
+                .wrapping_add(v3.rotate_left(12))
+                .wrapping_add(v4.rotate_left(18));
+            for &v in &self.v {
+                h = xxh64_merge(h, v);
+            }
+            h
+        } else {
+            self.seed.wrapping_add(PRIME64_5)
+        };
+        h = h.wrapping_add(self.length);
+
+        let mut rest = &self.buf[..self.nbuf];
+        while rest.len() >= 8 {
+            h ^= xxh64_round(0, read_u64(rest, 0));
+            h = h.rotate_left(27).wrapping_mul(PRIME64_1).wrapping_add(PRIME64_4);
+            rest = &rest[8..];
+        }
+        if rest.len() >= 4 {
+            h ^= u64::from(read_u32(rest, 0)).wrapping_mul(PRIME64_1);
+            h = h.rotate_left(23).wrapping_mul(PRIME64_2).wrapping_add(PRIME64_3);
+            rest = &rest[4..];
+        }
+        for &b in rest {
+            h ^= u64::from(b).wrapping_mul(PRIME64_5);
+            h = h.rotate_left(11).wrapping_mul(PRIME64_1);
+        }
+
+        xxh64_avalanche(h)
+    }
+}
+
+impl ResettableContext for Xxh64Context {
+    #[inline]
+    fn finish_reset(&mut self) -> u64 {
+        let result = self.clone().finish();
+        *self = Xxh64HashFunction::new_with_seed(self.seed).init();
+        result
+    }
+}
+
+impl HashFunction for Xxh64HashFunction {
+    type Context = Xxh64Context;
+
+    #[inline]
+    fn init(&self) -> Xxh64Context {
+        let seed = self.seed;
+        Xxh64Context {
+            seed,
+            v: [
+                seed.wrapping_add(PRIME64_1).wrapping_add(PRIME64_2),
+                seed.wrapping_add(PRIME64_2),
+                seed,
+                seed.wrapping_sub(PRIME64_1),
+            ],
+            length: 0,
+            buf: [0; 32],
+            nbuf: 0,
+        }
+    }
+}
+
+const SECRET_SIZE: usize = 192;
+
+const DEFAULT_SECRET: [u8; SECRET_SIZE] = [
+    0xb8, 0xfe, 0x6c, 0x39, 0x23, 0xa4, 0x4b, 0xbe, 0x7c, 0x01, 0x81, 0x2c, 0xf7, 0x21, 0xad, 0x1c,
+    0xde, 0xd4, 0x6d, 0xe9, 0x83, 0x90, 0x97, 0xdb, 0x72, 0x40, 0xa4, 0xa4, 0xb7, 0xb3, 0x67, 0x1f,
+    0xcb, 0x79, 0xe6, 0x4e, 0xcc, 0xc0, 0xe5, 0x78, 0x82, 0x5a, 0xd0, 0x7d, 0xcc, 0xff, 0x72, 0x21,
+    0xb8, 0x08, 0x46, 0x74, 0xf7, 0x43, 0x24, 0x8e, 0xe0, 0x35, 0x90, 0xe6, 0x81, 0x3a, 0x26, 0x4c,
+    0x3c, 0x28, 0x52, 0xbb, 0x91, 0xc3, 0x00, 0xcb, 0x88, 0xd0, 0x65, 0x8b, 0x1b, 0x53, 0x2e, 0xa3,
+    0x71, 0x64, 0x48, 0x97, 0xa2, 0x0d, 0xf9, 0x4e, 0x38, 0x19, 0xef, 0x46, 0xa9, 0xde, 0xac, 0xd8,
+    0xa8, 0xfa, 0x76, 0x3f, 0xe3, 0x9c, 0x34, 0x3f, 0xf9, 0xdc, 0xbb, 0xc7, 0xc7, 0x0b, 0x4f, 0x1d,
+    0x8a, 0x51, 0xe0, 0x4b, 0xcd, 0xb4, 0x59, 0x31, 0xc8, 0x9f, 0x7e, 0xc9, 0xd9, 0x78, 0x73, 0x64,
+    0xea, 0xc5, 0xac, 0x83, 0x34, 0xd3, 0xeb, 0xc3, 0xc5, 0x81, 0xa0, 0xff, 0xfa, 0x13, 0x63, 0xeb,
+    0x17, 0x0d, 0xdd, 0x51, 0xb7, 0xf0, 0xda, 0x49, 0xd3, 0x16, 0x55, 0x26, 0x29, 0xd4, 0x68, 0x9e,
+    0x2b, 0x16, 0xbe, 0x58, 0x7d, 0x47, 0xa1, 0xfc, 0x8f, 0xf8, 0xb8, 0xd1, 0x7a, 0xd0, 0x31, 0xce,
+    0x45, 0xcb, 0x3a, 0x8f, 0x95, 0x16, 0x04, 0x28, 0xaf, 0xd7, 0xfb, 0xca, 0xbb, 0x4b, 0x40, 0x7e,
+];
+
+const STRIPE_LEN: usize = 64;
+const STRIPES_PER_BLOCK: usize = (SECRET_SIZE - STRIPE_LEN) / 8;
+const BLOCK_LEN: usize = STRIPE_LEN * STRIPES_PER_BLOCK;
+const MIDSIZE_MAX: usize = 240;
+const BUFFER_SIZE: usize = 256;
+
+const INIT_ACC: [u64; 8] = [
+    PRIME32_3, PRIME64_1, PRIME64_2, PRIME64_3,
+    PRIME64_4, PRIME32_2, PRIME64_5, PRIME32_1,
+];
+
+#[inline(always)]
+fn mul128_fold64(a: u64, b: u64) -> u64 {
+    let product = u128::from(a) * u128::from(b);
+    (product as u64) ^ ((product >> 64) as u64)
+}
+
+#[inline(always)]
+fn xxh3_avalanche(mut h: u64) -> u64 {
+    h ^= h >> 37;
+    h = h.wrapping_mul(0x165667919E3779F9);
+    h ^ (h >> 32)
+}
+
+#[inline(always)]
+fn rrmxmx(mut h: u64, len: u64) -> u64 {
+    h ^= h.rotate_left(49) ^ h.rotate_left(24);
+    h = h.wrapping_mul(0x9FB21C651E98DF25);
+    h ^= (h >> 35).wrapping_add(len);
+    h = h.wrapping_mul(0x9FB21C651E98DF25);
+    h ^ (h >> 28)
+}
+
+#[inline(always)]
+fn mix16(input: &[u8], secret: &[u8], seed: u64) -> u64 {
+    mul128_fold64(read_u64(input, 0) ^ read_u64(secret, 0).wrapping_add(seed),
+                  read_u64(input, 8) ^ read_u64(secret, 8).wrapping_sub(seed))
+}
+
+/// XXH3 of an input of at most `MIDSIZE_MAX` bytes.
+fn xxh3_short(input: &[u8], secret: &[u8], seed: u64) -> u64 {
+    let len = input.len();
+    let len64 = len as u64;
+    match len {
+        0 => xxh64_avalanche(seed ^ read_u64(secret, 56) ^ read_u64(secret, 64)),
+        1..=3 => {
+            let combined = (u32::from(input[0]) << 16) | (u32::from(input[len >> 1]) << 24)
+                | u32::from(input[len - 1]) | ((len as u32) << 8);
+            let bitflip = u64::from(read_u32(secret, 0) ^ read_u32(secret, 4)).wrapping_add(seed);
+            xxh64_avalanche(u64::from(combined) ^ bitflip)
+        }
+        4..=8 => {
+            let seed = seed ^ (u64::from((seed as u32).swap_bytes()) << 32);
+            let bitflip = (read_u64(secret, 8) ^ read_u64(secret, 16)).wrapping_sub(seed);
+            let input64 = u64::from(read_u32(input, len - 4))
+                .wrapping_add(u64::from(read_u32(input, 0)) << 32);
+            rrmxmx(input64 ^ bitflip, len64)
+        }
+        9..=16 => {
+            let bitflip1 = (read_u64(secret, 24) ^ read_u64(secret, 32)).wrapping_add(seed);
+            let bitflip2 = (read_u64(secret, 40) ^ read_u64(secret, 48)).wrapping_sub(seed);
+            let lo = read_u64(input, 0) ^ bitflip1;
+            let hi = read_u64(input, len - 8) ^ bitflip2;
+            xxh3_avalanche(len64.wrapping_add(lo.swap_bytes())
+                           .wrapping_add(hi)
+                           .wrapping_add(mul128_fold64(lo, hi)))
+        }
+        17..=128 => {
+            let mut acc = len64.wrapping_mul(PRIME64_1);
+            let rounds = (len - 1) / 32;
+            for i in (0..=rounds).rev() {
+                acc = acc.wrapping_add(mix16(&input[16 * i..], &secret[32 * i..], seed));
+                acc = acc.wrapping_add(mix16(&input[len - 16 * (i + 1)..], &secret[32 * i + 16..], seed));
+            }
+            xxh3_avalanche(acc)
+        }
+        _ => {
+            let mut acc = len64.wrapping_mul(PRIME64_1);
+            for i in 0..8 {
+                acc = acc.wrapping_add(mix16(&input[16 * i..], &secret[16 * i..], seed));
+            }
+            acc = xxh3_avalanche(acc);
+            for i in 8..len / 16 {
+                acc = acc.wrapping_add(mix16(&input[16 * i..], &secret[16 * (i - 8) + 3..], seed));
+            }
+            acc = acc.wrapping_add(mix16(&input[len - 16..], &secret[136 - 17..], seed));
+            xxh3_avalanche(acc)
+        }
+    }
+}
+
+#[inline(always)]
+fn accumulate_512(acc: &mut [u64; 8], stripe: &[u8], secret: &[u8]) {
+    for i in 0..8 {
+        let data = read_u64(stripe, 8 * i);
+        let key = data ^ read_u64(secret, 8 * i);
+        acc[i ^ 1] = acc[i ^ 1].wrapping_add(data);
+        acc[i] = acc[i].wrapping_add((key & 0xffffffff).wrapping_mul(key >> 32));
+    }
+}
+
+#[inline(always)]
+fn scramble(acc: &mut [u64; 8], secret: &[u8]) {
+    for (i, a) in acc.iter_mut().enumerate() {
+        *a = (*a ^ (*a >> 47) ^ read_u64(secret, 8 * i)).wrapping_mul(PRIME32_1);
+    }
+}
+
+/// Accumulate `stripes` consecutive stripes of `input`, the first one
+/// having index `first` in its block.
+#[inline(always)]
+fn accumulate(acc: &mut [u64; 8], input: &[u8], secret: &[u8], first: usize, stripes: usize) {
+    for s in 0..stripes {
+        accumulate_512(acc, &input[s * STRIPE_LEN..], &secret[(first + s) * 8..]);
+    }
+}
+
+#[inline]
+fn merge_accs(acc: &[u64; 8], secret: &[u8], start: u64) -> u64 {
+    let mut result = start;
+    for i in 0..4 {
+        result = result.wrapping_add(mul128_fold64(acc[2 * i] ^ read_u64(secret, 16 * i),
+                                                   acc[2 * i + 1] ^ read_u64(secret, 16 * i + 8)));
+    }
+    xxh3_avalanche(result)
+}
+
+#[inline]
+fn finish_long(acc: &mut [u64; 8], last_stripe: &[u8], secret: &[u8], len: u64) -> u64 {
+    accumulate_512(acc, last_stripe, &secret[SECRET_SIZE - STRIPE_LEN - 7..]);
+    merge_accs(acc, &secret[11..], len.wrapping_mul(PRIME64_1))
+}
+
+/// One-shot XXH3 of an input longer than `MIDSIZE_MAX` bytes.
+fn xxh3_long(input: &[u8], secret: &[u8]) -> u64 {
+    let len = input.len();
+    let mut acc = INIT_ACC;
+    let blocks = (len - 1) / BLOCK_LEN;
+    for b in 0..blocks {
+        accumulate(&mut acc, &input[b * BLOCK_LEN..], secret, 0, STRIPES_PER_BLOCK);
+        scramble(&mut acc, &secret[SECRET_SIZE - STRIPE_LEN..]);
+    }
+    let stripes = ((len - 1) - blocks * BLOCK_LEN) / STRIPE_LEN;
+    accumulate(&mut acc, &input[blocks * BLOCK_LEN..], secret, 0, stripes);
+    finish_long(&mut acc, &input[len - STRIPE_LEN..], secret, len as u64)
+}
+
+/// XXH3-64, with a 64-bit seed.
+///
+/// As in the reference implementation, short inputs mix the seed with
+/// the default secret, while long ones use a secret derived from it.
+#[derive(Clone, Copy)]
+pub struct Xxh3HashFunction {
+    seed: u64,
+    secret: [u8; SECRET_SIZE],
+}
+
+impl Xxh3HashFunction {
+    #[inline]
+    pub fn new() -> Xxh3HashFunction {
+        Xxh3HashFunction::new_with_seed(0)
+    }
+
+    pub fn new_with_seed(seed: u64) -> Xxh3HashFunction {
+        let mut secret = DEFAULT_SECRET;
+        for (i, word) in secret.chunks_exact_mut(8).enumerate() {
+            let value = u64::from_le_bytes((&*word).try_into().unwrap());
+            let value = if i % 2 == 0 { value.wrapping_add(seed) } else { value.wrapping_sub(seed) };
+            word.copy_from_slice(&value.to_le_bytes());
+        }
+        Xxh3HashFunction { seed, secret }
+    }
+}
+
+impl Default for Xxh3HashFunction {
+    #[inline]
+    fn default() -> Xxh3HashFunction {
+        Xxh3HashFunction::new()
+    }
+}
+
+/// Streaming XXH3 state.
+///
+/// Input is buffered and consumed 4 stripes at a time, always keeping
+/// at least one byte buffered, so that `finish()` can process the last
+/// stripe like the one-shot algorithm does.
+#[derive(Clone)]
+pub struct Xxh3Context {
+    seed: u64,
+    secret: [u8; SECRET_SIZE],
+    acc: [u64; 8],
+    length: u64,              // how many bytes we've processed
+    stripes: usize,           // stripes accumulated in the current block
+    buf: [u8; BUFFER_SIZE],   // unprocessed bytes
+    nbuf: usize,              // how many bytes in buf are valid
+}
+
+impl Xxh3Context {
+    /// Accumulate `input`, made of whole stripes, continuing the current
+    /// block and scrambling at every block boundary.
+    fn consume(acc: &mut [u64; 8], done: &mut usize, input: &[u8], secret: &[u8]) {
+        let mut input = input;
+        while !input.is_empty() {
+            let n = core::cmp::min(STRIPES_PER_BLOCK - *done, input.len() / STRIPE_LEN);
+            accumulate(acc, input, secret, *done, n);
+            *done += n;
+            input = &input[n * STRIPE_LEN..];
+            if *done == STRIPES_PER_BLOCK {
+                scramble(acc, &secret[SECRET_SIZE - STRIPE_LEN..]);
+                *done = 0;
+            }
+        }
+    }
+}
+
+impl HashContext for Xxh3Context {
+    type Result = u64;
+
+    fn update(&mut self, msg: &[u8]) {
+        let mut msg = msg;
+        self.length += msg.len() as u64;
+
+        if self.nbuf + msg.len() <= BUFFER_SIZE {
+            self.buf[self.nbuf..self.nbuf + msg.len()].copy_from_slice(msg);
+            self.nbuf += msg.len();
+            return
+        }
+
+        if self.nbuf != 0 {
+            let n = BUFFER_SIZE - self.nbuf;
+            self.buf[self.nbuf..].copy_from_slice(&msg[..n]);
+            msg = &msg[n..];
+            Xxh3Context::consume(&mut self.acc, &mut self.stripes, &self.buf, &self.secret);
+            self.nbuf = 0;
+        }
+
+        // keep (at least) the final byte buffered
+        if msg.len() > BUFFER_SIZE {
+            let n = (msg.len() - 1) / BUFFER_SIZE * BUFFER_SIZE;
+            Xxh3Context::consume(&mut self.acc, &mut self.stripes, &msg[..n], &self.secret);
+            // the last stripe before the buffered bytes, in case fewer
+            // than a stripe end up buffered
+            self.buf[BUFFER_SIZE - STRIPE_LEN..].copy_from_slice(&msg[n - STRIPE_LEN..n]);
+            msg = &msg[n..];
+        }
+
+        self.buf[..msg.len()].copy_from_slice(msg);
+        self.nbuf = msg.len();
+    }
+
+    fn finish(self) -> u64 {
+        if self.length <= MIDSIZE_MAX as u64 {
+            return xxh3_short(&self.buf[..self.nbuf], &DEFAULT_SECRET, self.seed);
+        }
+
+        let mut acc = self.acc;
+        let mut last = [0u8; STRIPE_LEN];
+        if self.nbuf >= STRIPE_LEN {
+            let mut done = self.stripes;
+            let stripes = (self.nbuf - 1) / STRIPE_LEN;
+            Xxh3Context::consume(&mut acc, &mut done, &self.buf[..stripes * STRIPE_LEN], &self.secret);
+            last.copy_from_slice(&self.buf[self.nbuf - STRIPE_LEN..self.nbuf]);
+        } else {
+            // the last stripe straddles the previously consumed data
+            let before = STRIPE_LEN - self.nbuf;
+            last[..before].copy_from_slice(&self.buf[BUFFER_SIZE - before..]);
+            last[before..].copy_from_slice(&self.buf[..self.nbuf]);
+        }
+        finish_long(&mut acc, &last, &self.secret, self.length)
+    }
+}
+
+impl ResettableContext for Xxh3Context {
+    // The derived secret is kept, only the running state is reset
+    #[inline]
+    fn finish_reset(&mut self) -> u64 {
+        let result = self.clone().finish();
+        self.acc = INIT_ACC;
+        self.length = 0;
+        self.stripes = 0;
+        self.nbuf = 0;
+        result
+    }
+}
+
+impl HashFunction for Xxh3HashFunction {
+    type Context = Xxh3Context;
+
+    #[inline]
+    fn init(&self) -> Xxh3Context {
+        Xxh3Context {
+            seed: self.seed,
+            secret: self.secret,
+            acc: INIT_ACC,
+            length: 0,
+            stripes: 0,
+            buf: [0; BUFFER_SIZE],
+            nbuf: 0,
+        }
+    }
+
+    #[inline]
+    fn digest(&self, bytes: &[u8]) -> u64 {
+        if bytes.len() <= MIDSIZE_MAX {
+            xxh3_short(bytes, &DEFAULT_SECRET, self.seed)
+        } else {
+            xxh3_long(bytes, &self.secret)
+        }
+    }
+}
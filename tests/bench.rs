@@ -0,0 +1,24 @@
+use hash::bench::{compare_throughput, ALGORITHMS};
+
+#[test]
+fn one_positive_throughput_per_algorithm() {
+    let input = vec![0x5a; 4096];
+    let results = compare_throughput(&input, 10);
+    assert_eq!(results.len(), ALGORITHMS.len());
+    for (&(name, _), &(measured, throughput)) in ALGORITHMS.iter().zip(&results) {
+        assert_eq!(name, measured);
+        assert!(throughput > 0.0, "{}: {}", name, throughput);
+    }
+}
+
+#[test]
+#[should_panic]
+fn empty_input_is_rejected() {
+    compare_throughput(b"", 10);
+}
+
+#[test]
+#[should_panic]
+fn zero_iterations_are_rejected() {
+    compare_throughput(b"abc", 0);
+}
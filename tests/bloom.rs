@@ -0,0 +1,29 @@
+use hash::bloom::BloomFilter;
+
+#[test]
+fn reloaded_filter_answers_the_same() {
+    let mut filter = BloomFilter::new_with_keys(1000, 5, 0x0123_4567, 0x89ab_cdef);
+    for i in 0..100u32 {
+        filter.insert(&i);
+    }
+
+    let json = serde_json::to_string(&filter).unwrap();
+    let reloaded: BloomFilter = serde_json::from_str(&json).unwrap();
+    assert_eq!(reloaded, filter);
+    for i in 0..100u32 {
+        assert!(reloaded.contains(&i));
+    }
+    for i in 100..1000u32 {
+        assert_eq!(reloaded.contains(&i), filter.contains(&i));
+    }
+}
+
+#[test]
+fn malformed_filters_are_rejected() {
+    let parse = |json: &str| serde_json::from_str::<BloomFilter>(json);
+    assert!(parse(r#"{"bits":[0,0],"num_bits":100,"num_hashes":3,"k0":0,"k1":0}"#).is_ok());
+    assert!(parse(r#"{"bits":[],"num_bits":0,"num_hashes":3,"k0":0,"k1":0}"#).is_err());
+    assert!(parse(r#"{"bits":[0,0],"num_bits":100,"num_hashes":0,"k0":0,"k1":0}"#).is_err());
+    assert!(parse(r#"{"bits":[0],"num_bits":100,"num_hashes":3,"k0":0,"k1":0}"#).is_err());
+    assert!(parse(r#"{"bits":[0,0,0],"num_bits":100,"num_hashes":3,"k0":0,"k1":0}"#).is_err());
+}
@@ -0,0 +1,256 @@
+use hash::bucket::sticky_route;
+use hash::sip::SipHashFunction;
+use hash::traits::{Hash, HashFunction};
+use hash::util::fast_range;
+
+const K0: u64 = 0x0706050403020100;
+const K1: u64 = 0x0f0e0d0c0b0a0908;
+
+/// Chi-square statistic of `counts` against a uniform distribution.
+fn chi_square(counts: &[u64]) -> f64 {
+    let total: u64 = counts.iter().sum();
+    let expected = total as f64 / counts.len() as f64;
+    counts.iter().map(|&c| (c as f64 - expected).powi(2) / expected).sum()
+}
+
+#[test]
+fn buckets_are_uniform() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    // not a power of two; the critical value for 36 degrees of freedom
+    // at p = 0.0001 is about 77
+    let mut counts = [0u64; 37];
+    for i in 0..100_000u32 {
+        let b = f.bucket_bytes(&i.to_le_bytes(), 37);
+        counts[b as usize] += 1;
+    }
+    assert!(chi_square(&counts) < 77.0, "{:?}", counts);
+
+    assert_eq!(f.bucket_bytes(b"key", 37), f.bucket_bytes(b"key", 37));
+    assert_eq!(f.bucket_bytes(b"key", 1), 0);
+}
+
+#[test]
+fn buckets_use_the_high_bits() {
+    assert_eq!(fast_range(0, 10), 0);
+    assert_eq!(fast_range(u64::MAX, 10), 9);
+    assert_eq!(fast_range(1 << 63, 10), 5);
+    assert_eq!(fast_range(u64::MAX, u64::MAX), u64::MAX - 1);
+
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let key: &[u8] = b"key";
+    assert_eq!(u64::from(f.bucket_bytes(key, 37)), fast_range(f.digest(key), 37));
+    // the generic `bucket()` hashes the key as a value, length prefix
+    // included
+    assert_eq!(f.bucket(key, 37) as u64, fast_range(key.digest(&f), 37));
+    assert_eq!(f.bucket(&42u32, 37) as u64, fast_range(42u32.digest(&f), 37));
+}
+
+#[test]
+fn sessions_stick_to_spread_backends() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let backends = ["a", "b", "c", "d", "e"];
+    let mut counts = [0u64; 5];
+    for i in 0..10_000u32 {
+        let session = i.to_le_bytes();
+        let backend = sticky_route(&session, &backends, &f);
+        assert_eq!(sticky_route(&session, &backends, &f), backend);
+        counts[backends.iter().position(|b| b == backend).unwrap()] += 1;
+    }
+    // 4 degrees of freedom, p = 0.0001
+    assert!(chi_square(&counts) < 23.5, "{:?}", counts);
+
+    assert_eq!(*sticky_route(b"session", &["only"], &f), "only");
+}
+
+#[test]
+fn replica_shards_are_distinct() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    for i in 0..1000u32 {
+        let key = i.to_le_bytes();
+        let shards = f.shards(&key, 10, 3);
+        assert_eq!(shards.len(), 3);
+        assert!(shards.iter().all(|&s| s < 10));
+        assert!(shards[0] != shards[1] && shards[0] != shards[2] && shards[1] != shards[2]);
+        assert_eq!(f.shards(&key, 10, 3), shards);
+        // the primary shard does not depend on the replica count
+        assert_eq!(f.shards(&key, 10, 1), [shards[0]]);
+    }
+
+    // more replicas than shards are clamped to every shard
+    let mut all = f.shards(b"key", 4, 10);
+    all.sort_unstable();
+    assert_eq!(all, [0, 1, 2, 3]);
+    assert!(f.shards(b"key", 0, 3).is_empty());
+}
+
+#[test]
+fn stable_ids_survive_new_names() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let assign = |names: &[&str]| -> Vec<u32> {
+        names.iter().map(|name| f.stable_id(name, 1 << 16)).collect()
+    };
+
+    let v1 = assign(&["Created", "Updated", "Deleted"]);
+    let v2 = assign(&["Archived", "Created", "Restored", "Updated", "Deleted"]);
+    assert_eq!(v1, [v2[1], v2[3], v2[4]]);
+    assert!(v2.iter().all(|&id| id < 1 << 16));
+
+    // collisions must be checked by the caller, here there are none
+    let mut sorted = v2.clone();
+    sorted.sort_unstable();
+    sorted.dedup();
+    assert_eq!(sorted.len(), v2.len());
+}
+
+#[test]
+fn jump_hash_moves_keys_only_to_the_new_bucket() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let keys: Vec<u64> = (0..10_000u32).map(|i| f.digest(&i.to_le_bytes())).collect();
+    for n in [1u32, 2, 7, 10, 63] {
+        let mut moved = 0;
+        for &key in &keys {
+            let before = f.jump_hash(key, n);
+            let after = f.jump_hash(key, n + 1);
+            assert!(before < n);
+            if after != before {
+                assert_eq!(after, n);
+                moved += 1;
+            }
+        }
+        // about 1 / (n + 1) of the keys move
+        let expected = keys.len() as f64 / f64::from(n + 1);
+        assert!((moved as f64 - expected).abs() < expected * 0.15 + 50.0, "{} {}", n, moved);
+    }
+    assert_eq!(f.jump_hash(12345, 1), 0);
+}
+
+#[test]
+fn two_choices_are_distinct() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let mut first_counts = [0u64; 5];
+    let mut second_counts = [0u64; 5];
+    for i in 0..10_000u32 {
+        let key = i.to_le_bytes();
+        let (a, b) = f.two_choices(&key, 5);
+        assert!(a < 5 && b < 5 && a != b);
+        assert_eq!(f.two_choices(&key, 5), (a, b));
+        first_counts[a as usize] += 1;
+        second_counts[b as usize] += 1;
+    }
+    assert!(chi_square(&first_counts) < 23.5, "{:?}", first_counts);
+    assert!(chi_square(&second_counts) < 23.5, "{:?}", second_counts);
+
+    let (a, b) = f.two_choices(b"key", 2);
+    assert_eq!(a + b, 1);
+}
+
+#[test]
+fn partitions_are_stable_and_balanced() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let keys: Vec<[u8; 4]> = (0..10_000u32).map(|i| i.to_le_bytes()).collect();
+    let keys: Vec<&[u8]> = keys.iter().map(|k| &k[..]).collect();
+
+    let groups = f.partition_balanced(&keys, 8);
+    assert_eq!(groups.len(), 8);
+    assert_eq!(groups.iter().map(Vec::len).sum::<usize>(), keys.len());
+    for (worker, group) in groups.iter().enumerate() {
+        assert!(group.windows(2).all(|w| w[0] < w[1]));
+        for &i in group {
+            assert_eq!(f.partition(keys[i], 8), worker as u32);
+        }
+    }
+    assert_eq!(f.partition_balanced(&keys, 8), groups);
+
+    // 7 degrees of freedom, p = 0.0001
+    let counts: Vec<u64> = groups.iter().map(|g| g.len() as u64).collect();
+    assert!(chi_square(&counts) < 29.9, "{:?}", counts);
+}
+
+#[test]
+fn experiment_buckets_are_stable_and_independent() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let mut counts = [0u64; 4];
+    let mut same = 0;
+    for i in 0..10_000u32 {
+        let user = i.to_le_bytes();
+        let variant = f.experiment_bucket(&user, "checkout", 4);
+        assert_eq!(f.experiment_bucket(&user, "checkout", 4), variant);
+        counts[variant as usize] += 1;
+        if f.experiment_bucket(&user, "search", 4) == variant {
+            same += 1;
+        }
+    }
+    // 3 degrees of freedom, p = 0.0001
+    assert!(chi_square(&counts) < 21.2, "{:?}", counts);
+    // independent experiments agree for about a quarter of the users
+    assert!((2200..2800).contains(&same), "{}", same);
+
+    // the name is framed, so it cannot blend into the user id
+    let n = 1 << 20;
+    assert_ne!(f.experiment_bucket(b"bc", "a", n), f.experiment_bucket(b"c", "ab", n));
+}
+
+#[test]
+fn experiments_and_rollouts_of_the_same_name_are_independent() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    // without separate domains, the rollout would be exactly the users
+    // whose experiment position is below the percentage
+    let mut agree = 0;
+    for i in 0..10_000u32 {
+        let user = i.to_le_bytes();
+        if (f.experiment_bucket(&user, "x", 100) < 30) == f.in_rollout("x", &user, 30) {
+            agree += 1;
+        }
+    }
+    // independent positions agree for about 0.3^2 + 0.7^2 = 58% of the users
+    assert!((5500..6100).contains(&agree), "{}", agree);
+}
+
+#[test]
+fn rate_limit_shards_rotate_with_the_window() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let mut counts = [0u64; 16];
+    let mut moved = 0;
+    for i in 0..10_000u32 {
+        let client = i.to_le_bytes();
+        let shard = f.rate_limit_shard(&client, 16, 42);
+        assert_eq!(f.rate_limit_shard(&client, 16, 42), shard);
+        counts[shard as usize] += 1;
+        if f.rate_limit_shard(&client, 16, 43) != shard {
+            moved += 1;
+        }
+    }
+    // 15 degrees of freedom, p = 0.0001
+    assert!(chi_square(&counts) < 42.6, "{:?}", counts);
+    // a new window reshuffles about 15/16 of the clients
+    assert!((9_100..9_650).contains(&moved), "{}", moved);
+
+    assert_eq!(f.rate_limit_shard(b"client", 1, 42), 0);
+}
+
+#[test]
+fn rollouts_are_monotonic_and_independent() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let (mut at_30, mut at_50, mut both_flags) = (0, 0, 0);
+    for i in 0..10_000u32 {
+        let user = i.to_le_bytes();
+        let enabled = f.in_rollout("dark-mode", &user, 30);
+        assert_eq!(f.in_rollout("dark-mode", &user, 30), enabled);
+        if enabled {
+            at_30 += 1;
+            assert!(f.in_rollout("dark-mode", &user, 50));
+            if f.in_rollout("new-search", &user, 30) {
+                both_flags += 1;
+            }
+        }
+        if f.in_rollout("dark-mode", &user, 50) {
+            at_50 += 1;
+        }
+        assert!(!f.in_rollout("dark-mode", &user, 0));
+        assert!(f.in_rollout("dark-mode", &user, 100));
+    }
+    assert!((2_800..3_200).contains(&at_30), "{}", at_30);
+    assert!((4_800..5_200).contains(&at_50), "{}", at_50);
+    // independent flags: 30% of 30%
+    assert!((750..1_050).contains(&both_flags), "{}", both_flags);
+}
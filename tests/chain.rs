@@ -0,0 +1,39 @@
+use hash::chain::HashChain;
+use hash::sip::SipHashFunction;
+use hash::traits::{HashContext, HashFunction};
+
+fn head(entries: &[&[u8]]) -> u64 {
+    let mut chain = HashChain::new(SipHashFunction::new_with_keys(1, 2));
+    for entry in entries {
+        chain.append(entry);
+    }
+    chain.head()
+}
+
+#[test]
+fn heads_link_every_entry() {
+    let f = SipHashFunction::new_with_keys(1, 2);
+    let mut chain = HashChain::new(f);
+    assert_eq!(chain.head(), 0);
+
+    let first = chain.append(b"first");
+    let mut ctx = f.init();
+    ctx.update(&0u64.to_le_bytes());
+    ctx.update(b"first");
+    assert_eq!(first, ctx.finish());
+
+    let mut ctx = f.init();
+    ctx.update(&first.to_le_bytes());
+    ctx.update(b"second");
+    assert_eq!(chain.append(b"second"), ctx.finish());
+    assert_eq!(chain.head(), head(&[b"first", b"second"]));
+}
+
+#[test]
+fn tampering_changes_the_head() {
+    let original = head(&[b"alice", b"bob", b"carol"]);
+    assert_eq!(original, head(&[b"alice", b"bob", b"carol"]));
+    assert_ne!(original, head(&[b"alice", b"mallory", b"carol"]));
+    assert_ne!(original, head(&[b"alice", b"carol", b"bob"]));
+    assert_ne!(original, head(&[b"alice", b"bob"]));
+}
@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+
+use hash::sip::{SipBuildHasher, SipHashFunction, StdHasher};
+use hash::traits::HashFunction;
+
+const K0: u64 = 0x0706050403020100;
+const K1: u64 = 0x0f0e0d0c0b0a0908;
+
+#[test]
+fn std_hashers_work_in_hash_maps() {
+    let mut map: HashMap<&str, u32, std::hash::BuildHasherDefault<StdHasher>> = HashMap::default();
+    map.insert("one", 1);
+    map.insert("two", 2);
+    map.insert("one", 3);
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get("one"), Some(&3));
+    assert_eq!(map.get("two"), Some(&2));
+    assert_eq!(map.get("three"), None);
+}
+
+#[test]
+fn std_hasher_finish_does_not_consume_the_state() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let mut hasher = StdHasher::new(f.init());
+    hasher.write(b"abc");
+    assert_eq!(hasher.finish(), f.digest(b"abc"));
+    assert_eq!(hasher.finish(), f.digest(b"abc"));
+    hasher.write(b"def");
+    assert_eq!(hasher.finish(), f.digest(b"abcdef"));
+}
+
+fn std_digest<B: BuildHasher>(build: &B, key: &str) -> u64 {
+    build.hash_one(key)
+}
+
+#[test]
+fn build_hashers_draw_fresh_keys() {
+    assert_ne!(std_digest(&SipBuildHasher::new(), "key"), std_digest(&SipBuildHasher::new(), "key"));
+    assert_eq!(std_digest(&SipBuildHasher::with_keys(7, 39), "key"),
+               std_digest(&SipBuildHasher::with_keys(7, 39), "key"));
+
+    let build = SipBuildHasher::new();
+    assert_eq!(std_digest(&build, "key"), std_digest(&build.clone(), "key"));
+
+    let mut map = HashMap::with_hasher(SipBuildHasher::with_keys(7, 39));
+    map.insert("key", 1);
+    assert_eq!(map.get("key"), Some(&1));
+}
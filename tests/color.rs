@@ -0,0 +1,27 @@
+use std::collections::HashSet;
+
+use hash::sip::SipHashFunction;
+
+const K0: u64 = 0x0706050403020100;
+const K1: u64 = 0x0f0e0d0c0b0a0908;
+
+#[test]
+fn colors_are_stable_and_moderate() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let mut colors = HashSet::new();
+    for i in 0..1000u32 {
+        let id = i.to_le_bytes();
+        let (r, g, b) = f.to_color(&id);
+        assert_eq!(f.to_color(&id), (r, g, b));
+        colors.insert((r, g, b));
+
+        // HSL lightness and saturation, allowing for the rounding to u8
+        let max = f64::from(r.max(g).max(b)) / 255.0;
+        let min = f64::from(r.min(g).min(b)) / 255.0;
+        let lightness = (max + min) / 2.0;
+        let saturation = (max - min) / (1.0 - (2.0 * lightness - 1.0).abs());
+        assert!((0.39..=0.61).contains(&lightness), "{:?}: {}", (r, g, b), lightness);
+        assert!((0.43..=0.82).contains(&saturation), "{:?}: {}", (r, g, b), saturation);
+    }
+    assert!(colors.len() > 990, "{}", colors.len());
+}
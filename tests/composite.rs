@@ -0,0 +1,21 @@
+use hash::composite::CompositeKey;
+use hash::sip::SipHashFunction;
+
+#[test]
+fn parts_are_typed_and_framed() {
+    let f = SipHashFunction::new_with_keys(1, 2);
+    let key = |k: CompositeKey| k.finish(&f);
+
+    let a = key(CompositeKey::new().add_u64(5).add_str("5"));
+    assert_eq!(a, key(CompositeKey::new().add_u64(5).add_str("5")));
+    assert_ne!(a, key(CompositeKey::new().add_str("5").add_u64(5)));
+
+    // same bytes, different types
+    assert_ne!(key(CompositeKey::new().add_str("ab")), key(CompositeKey::new().add_bytes(b"ab")));
+    assert_ne!(key(CompositeKey::new().add_u64(0x6261)),
+               key(CompositeKey::new().add_bytes(&0x6261u64.to_le_bytes())));
+    // parts do not blend into each other
+    assert_ne!(key(CompositeKey::new().add_str("ab").add_str("c")),
+               key(CompositeKey::new().add_str("a").add_str("bc")));
+    assert_ne!(key(CompositeKey::new()), key(CompositeKey::new().add_bytes(b"")));
+}
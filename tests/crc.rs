@@ -0,0 +1,67 @@
+use hash::crc::{Crc32HashFunction, Crc32cHashFunction};
+use hash::traits::{HashContext, HashFunction};
+
+/// CRC-32 (IEEE) and CRC-32C (Castagnoli) checksums, the first one
+/// being the standard check value of both.
+const STRINGS: [(&[u8], u32, u32); 5] = [
+    (b"123456789", 0xcbf43926, 0xe3069283),
+    (b"", 0x00000000, 0x00000000),
+    (b"a", 0xe8b7be43, 0xc1d04330),
+    (b"abc", 0x352441c2, 0x364b3fb7),
+    (b"The quick brown fox jumps over the lazy dog", 0x414fa339, 0x22620404),
+];
+
+/// Same as `STRINGS`, for the first `len` bytes of `pattern()`.
+const LENGTHS: [(usize, u32, u32); 16] = [
+    (0, 0x00000000, 0x00000000),
+    (1, 0xd202ef8d, 0x527d5351),
+    (3, 0xf37aca27, 0x2443ae90),
+    (4, 0x62210d65, 0x2efc7ae8),
+    (7, 0xac7026df, 0x0a18b05b),
+    (8, 0x3bcd3a9e, 0x31ba006e),
+    (9, 0x76e8e6da, 0xe645f2ae),
+    (15, 0x57ac00f3, 0xff1a7275),
+    (16, 0x995197ca, 0xe3990f71),
+    (17, 0x64fd9327, 0x17f26639),
+    (128, 0x769a87c4, 0xfa10896d),
+    (129, 0x42cb689b, 0xd66d1196),
+    (240, 0xc788b151, 0xfeba9edf),
+    (241, 0x6f789520, 0x8f6302e5),
+    (1024, 0xd1ebf94b, 0xb52f1bd4),
+    (5000, 0x510c4bc5, 0x1e33c15e),
+];
+
+/// The bytes the length vectors are computed on.
+fn pattern(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 31 % 251) as u8).collect()
+}
+
+/// Feed `data` to `ctx` in `chunk`-byte updates.
+fn update_chunks<C: HashContext>(ctx: &mut C, data: &[u8], chunk: usize) {
+    for piece in data.chunks(chunk) {
+        ctx.update(piece);
+    }
+}
+
+#[test]
+fn reference_vectors() {
+    let (crc32, crc32c) = (Crc32HashFunction::new(), Crc32cHashFunction::new());
+    for &(msg, expected, expected_c) in &STRINGS {
+        assert_eq!(crc32.digest(msg), expected, "{:?}", msg);
+        assert_eq!(crc32c.digest(msg), expected_c, "{:?}", msg);
+    }
+    let data = pattern(5000);
+    for &(len, expected, expected_c) in &LENGTHS {
+        assert_eq!(crc32.digest(&data[..len]), expected, "length {}", len);
+        assert_eq!(crc32c.digest(&data[..len]), expected_c, "length {}", len);
+        for &chunk in &[1, 3, 16, 100] {
+            let mut ctx = crc32.init();
+            update_chunks(&mut ctx, &data[..len], chunk);
+            assert_eq!(ctx.finish(), expected, "length {}, chunks of {}", len, chunk);
+
+            let mut ctx = crc32c.init();
+            update_chunks(&mut ctx, &data[..len], chunk);
+            assert_eq!(ctx.finish(), expected_c, "length {}, chunks of {}", len, chunk);
+        }
+    }
+}
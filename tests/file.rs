@@ -0,0 +1,108 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use hash::log::LogHasher;
+use hash::sip::SipHashFunction;
+use hash::traits::{HashContext, HashFunction};
+
+/// A file in the temporary directory, removed when dropped.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(name: &str, contents: &[u8]) -> TempFile {
+        let path = std::env::temp_dir().join(format!("rust-hash-{}-{}", std::process::id(), name));
+        fs::write(&path, contents).unwrap();
+        TempFile(path)
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+fn contents(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 31 % 251) as u8).collect()
+}
+
+#[test]
+fn file_digests_combine_the_chunks() {
+    let f = SipHashFunction::new_with_keys(1, 2);
+    let data = contents(10_000);
+    let file = TempFile::new("combine", &data);
+
+    let mut ctx = f.init();
+    ctx.update(&10_000u64.to_le_bytes());
+    ctx.update(&4096u64.to_le_bytes());
+    for chunk in data.chunks(4096) {
+        ctx.update(&f.digest(chunk).to_le_bytes());
+    }
+    assert_eq!(f.digest_file(&file.0, 4096).unwrap(), ctx.finish());
+    assert_ne!(f.digest_file(&file.0, 4096).unwrap(), f.digest_file(&file.0, 1024).unwrap());
+
+    let empty = TempFile::new("empty", b"");
+    assert!(f.digest_file(&empty.0, 4096).is_ok());
+    assert!(f.digest_file(std::env::temp_dir().join("rust-hash-missing"), 4096).is_err());
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_file_digests_match_serial() {
+    let f = SipHashFunction::new_with_keys(1, 2);
+    let file = TempFile::new("parallel", &contents(1 << 20));
+    let serial = f.digest_file(&file.0, 4096).unwrap();
+    assert_eq!(f.digest_file_parallel(&file.0, 4096).unwrap(), serial);
+    assert_eq!(f.digest_file_parallel(&file.0, 4096).unwrap(), serial);
+    assert_eq!(f.digest_file_parallel(&file.0, 1000).unwrap(), f.digest_file(&file.0, 1000).unwrap());
+}
+
+#[test]
+fn log_digests_follow_appends() {
+    let f = SipHashFunction::new_with_keys(1, 2);
+    let data = contents(10_000);
+    let file = TempFile::new("log", &data[..3000]);
+
+    let mut log = LogHasher::new(&f, &file.0);
+    log.update_to_end().unwrap();
+    assert_eq!(log.offset(), 3000);
+    assert_eq!(log.digest(), f.digest(&data[..3000]));
+
+    let mut appender = OpenOptions::new().append(true).open(&file.0).unwrap();
+    appender.write_all(&data[3000..]).unwrap();
+    log.update_to_end().unwrap();
+    assert_eq!(log.offset(), 10_000);
+    assert_eq!(log.digest(), f.digest(&data));
+
+    // nothing new
+    log.update_to_end().unwrap();
+    assert_eq!(log.digest(), f.digest(&data));
+
+    fs::write(&file.0, &data[..10]).unwrap();
+    assert!(log.update_to_end().is_err());
+}
+
+#[test]
+fn log_digests_resume_from_saved_state() {
+    let f = SipHashFunction::new_with_keys(1, 2);
+    let data = contents(10_000);
+    let file = TempFile::new("resume", &data[..3000]);
+
+    let mut log = LogHasher::new(&f, &file.0);
+    log.update_to_end().unwrap();
+    let (offset, ctx) = log.into_parts();
+    assert_eq!(offset, 3000);
+
+    // after a restart, only the appended bytes are hashed
+    let mut appender = OpenOptions::new().append(true).open(&file.0).unwrap();
+    appender.write_all(&data[3000..]).unwrap();
+    let mut log = LogHasher::<SipHashFunction>::resume(&file.0, offset, ctx);
+    log.update_to_end().unwrap();
+    assert_eq!(log.offset(), 10_000);
+    assert_eq!(log.digest(), f.digest(&data));
+
+    // resuming past the end of the file fails like a truncation
+    let mut log = LogHasher::<SipHashFunction>::resume(&file.0, 20_000, f.init());
+    assert!(log.update_to_end().is_err());
+}
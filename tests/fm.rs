@@ -0,0 +1,27 @@
+use hash::fm::FmSketch;
+use hash::sip::SipHashFunction;
+
+#[test]
+fn empty_sketch_estimates_zero() {
+    let f = SipHashFunction::new_with_keys(1, 2);
+    assert_eq!(FmSketch::new(f).estimate(), 0.0);
+}
+
+#[test]
+fn estimates_ignore_duplicates() {
+    let f = SipHashFunction::new_with_keys(1, 2);
+    let mut sketch = FmSketch::new(f);
+    for i in 0..10_000u32 {
+        sketch.add(&i);
+    }
+    let estimate = sketch.estimate();
+    // a single bitmap is only accurate within a factor of a few
+    assert!((2_500.0..40_000.0).contains(&estimate), "{}", estimate);
+
+    for _ in 0..5 {
+        for i in 0..10_000u32 {
+            sketch.add(&i);
+        }
+    }
+    assert_eq!(sketch.estimate(), estimate);
+}
@@ -0,0 +1,28 @@
+use hash::fnv::FnvHashFunction;
+use hash::traits::{HashContext, HashFunction};
+
+/// FNV-1a 64-bit, from the reference test suite of the FNV authors.
+const VECTORS: [(&[u8], u64); 8] = [
+    (b"", 0xcbf29ce484222325),
+    (b"a", 0xaf63dc4c8601ec8c),
+    (b"b", 0xaf63df4c8601f1a5),
+    (b"c", 0xaf63de4c8601eff2),
+    (b"foo", 0xdcb27518fed9d577),
+    (b"foobar", 0x85944171f73967e8),
+    (b"chongo was here!\n", 0x46810940eff5f915),
+    (b"\xff\x00\x00\x01", 0x6961196491cc682d),
+];
+
+#[test]
+fn reference_vectors() {
+    let f = FnvHashFunction::new();
+    for &(msg, expected) in &VECTORS {
+        assert_eq!(f.digest(msg), expected, "{:?}", msg);
+
+        let mut ctx = f.init();
+        for b in msg {
+            ctx.update(std::slice::from_ref(b));
+        }
+        assert_eq!(ctx.finish(), expected, "byte by byte, {:?}", msg);
+    }
+}
@@ -0,0 +1,60 @@
+use hash::fx::FxHashFunction;
+use hash::traits::{HashContext, HashFunction};
+
+/// Output of `rustc_hash::FxHasher` (1.x) for a single `write()`.
+const STRINGS: [(&[u8], u64); 4] = [
+    (b"", 0x0000000000000000),
+    (b"a", 0xe0456665d3e60275),
+    (b"abc", 0xc360d75917ea8923),
+    (b"The quick brown fox jumps over the lazy dog", 0x9a54f56cab9c861b),
+];
+
+/// Same as `STRINGS`, for the first `len` bytes of `pattern()`.
+const LENGTHS: [(usize, u64); 16] = [
+    (0, 0x0000000000000000),
+    (1, 0x0000000000000000),
+    (3, 0xbffa39f3bea96581),
+    (4, 0x8f0805d3d05e0b00),
+    (7, 0x308fcceb4ced2efa),
+    (8, 0x1d2f5cffd05e0b00),
+    (9, 0xb35174e083ad4017),
+    (15, 0x30386fee9918855d),
+    (16, 0x24a61f5e56028c17),
+    (17, 0xc006e94ffbc75de5),
+    (128, 0x513ab9f09fafd20f),
+    (129, 0x154e23f99e953235),
+    (240, 0x65f2293e8c517917),
+    (241, 0xf28976e28dfff8d1),
+    (1024, 0x6ce60d0fe537f474),
+    (5000, 0x17431ccf49299d26),
+];
+
+/// The bytes the length vectors are computed on.
+fn pattern(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 31 % 251) as u8).collect()
+}
+
+/// Feed `data` to `ctx` in `chunk`-byte updates.
+fn update_chunks<C: HashContext>(ctx: &mut C, data: &[u8], chunk: usize) {
+    for piece in data.chunks(chunk) {
+        ctx.update(piece);
+    }
+}
+
+#[test]
+fn reference_vectors() {
+    let f = FxHashFunction::new();
+    for &(msg, expected) in &STRINGS {
+        assert_eq!(f.digest(msg), expected, "{:?}", msg);
+    }
+    let data = pattern(5000);
+    for &(len, expected) in &LENGTHS {
+        assert_eq!(f.digest(&data[..len]), expected, "length {}", len);
+        // unlike `FxHasher`, the context does not depend on the splits
+        for &chunk in &[1, 3, 8, 100] {
+            let mut ctx = f.init();
+            update_chunks(&mut ctx, &data[..len], chunk);
+            assert_eq!(ctx.finish(), expected, "length {}, chunks of {}", len, chunk);
+        }
+    }
+}
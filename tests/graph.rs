@@ -0,0 +1,21 @@
+use hash::sip::SipHashFunction;
+
+const K0: u64 = 0x0706050403020100;
+const K1: u64 = 0x0f0e0d0c0b0a0908;
+
+#[test]
+fn relabeled_graphs_digest_equally() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    // a path 0-1-2-3 with a pendant 4 on node 1
+    let graph = [(0, 1), (1, 2), (2, 3), (1, 4)];
+    // the same graph with nodes renamed by 0->3, 1->0, 2->4, 3->1, 4->2,
+    // edges listed in another order and reversed
+    let relabeled = [(2, 0), (1, 4), (0, 4), (0, 3)];
+    assert_eq!(f.digest_graph(&graph, 5), f.digest_graph(&relabeled, 5));
+
+    // a path 0-1-2-3-4 has the same number of nodes and edges
+    let path = [(0, 1), (1, 2), (2, 3), (3, 4)];
+    assert_ne!(f.digest_graph(&graph, 5), f.digest_graph(&path, 5));
+    // an isolated node changes the graph
+    assert_ne!(f.digest_graph(&graph, 5), f.digest_graph(&graph, 6));
+}
@@ -0,0 +1,43 @@
+use std::collections::BTreeSet;
+
+use hash::iblt::Iblt;
+
+fn table(keys: impl IntoIterator<Item = u64>) -> Iblt {
+    let mut table = Iblt::new_with_keys(120, 3, 1, 2);
+    for key in keys {
+        table.insert(key);
+    }
+    table
+}
+
+fn sorted(keys: Vec<u64>) -> Vec<u64> {
+    keys.into_iter().collect::<BTreeSet<_>>().into_iter().collect()
+}
+
+#[test]
+fn subtracted_tables_decode_the_symmetric_difference() {
+    let alice: Vec<u64> = (0..1000).chain([5000, 5001, 5002]).collect();
+    let bob: Vec<u64> = (3..1000).chain([6000, 6001]).collect();
+
+    let (only_alice, only_bob) = table(alice).subtract(&table(bob)).decode().unwrap();
+    assert_eq!(sorted(only_alice), [0, 1, 2, 5000, 5001, 5002]);
+    assert_eq!(sorted(only_bob), [6000, 6001]);
+
+    let (a, b) = table(0..100).subtract(&table(0..100)).decode().unwrap();
+    assert!(a.is_empty() && b.is_empty());
+}
+
+#[test]
+fn removed_keys_cancel_inserted_ones() {
+    let mut t = table(0..50);
+    for key in 0..50 {
+        t.remove(key);
+    }
+    t.insert(7);
+    assert_eq!(t.decode(), Some((vec![7], vec![])));
+}
+
+#[test]
+fn overfull_tables_do_not_decode() {
+    assert_eq!(table(0..500).subtract(&table(1000..1500)).decode(), None);
+}
@@ -0,0 +1,52 @@
+use std::hash::BuildHasher;
+
+use hash::sip::{SipBuildHasher, SipHashFunction};
+use hash::traits::HashFunction;
+use indexmap::IndexMap;
+
+const K0: u64 = 0x0706050403020100;
+const K1: u64 = 0x0f0e0d0c0b0a0908;
+
+#[test]
+fn index_map_with_sip_build_hasher() {
+    let mut map: IndexMap<u32, u32, SipBuildHasher> =
+        IndexMap::with_hasher(SipBuildHasher::with_keys(K0, K1));
+    for i in (0..100).rev() {
+        map.insert(i, i * i);
+    }
+    map.insert(50, 0);
+
+    assert_eq!(map.len(), 100);
+    assert_eq!(map.get(&7), Some(&49));
+    assert_eq!(map.get(&50), Some(&0));
+    assert_eq!(map.get(&100), None);
+    // insertion order, not key order; overwriting keeps the position
+    assert!(map.keys().copied().eq((0..100).rev()));
+
+    // keys are bucketed with this crate's SipHash
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    assert_eq!(map.hasher().hash_one(7u32), f.digest(&7u32.to_ne_bytes()));
+
+    let mut default: IndexMap<&str, u32, SipBuildHasher> = IndexMap::default();
+    default.insert("b", 2);
+    default.insert("a", 1);
+    assert_eq!(default.first(), Some((&"b", &2)));
+}
+
+#[test]
+fn index_maps_hash_in_insertion_order() {
+    use std::collections::HashMap;
+
+    use hash::traits::Hash;
+
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let forward: IndexMap<u32, &str> = IndexMap::from([(1, "one"), (2, "two")]);
+    let backward: IndexMap<u32, &str> = IndexMap::from([(2, "two"), (1, "one")]);
+    assert_ne!(forward.digest(&f), backward.digest(&f));
+    assert_eq!(forward.digest(&f), (2u64, (1u32, "one"), (2u32, "two")).digest(&f));
+
+    // unlike HashMaps, which do not depend on the iteration order
+    let forward: HashMap<u32, &str> = forward.into_iter().collect();
+    let backward: HashMap<u32, &str> = backward.into_iter().collect();
+    assert_eq!(forward.digest(&f), backward.digest(&f));
+}
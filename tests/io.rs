@@ -0,0 +1,75 @@
+use std::io::{self, Cursor, Write};
+
+use hash::io::HashWriter;
+use hash::sip::SipHashFunction;
+use hash::traits::{HashContext, HashFunction};
+
+const K0: u64 = 0x0706050403020100;
+const K1: u64 = 0x0f0e0d0c0b0a0908;
+
+fn contents(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 31 % 251) as u8).collect()
+}
+
+/// Writer accepting at most `chunk` bytes per call, and failing once it
+/// holds `capacity` bytes.
+struct Limited {
+    data: Vec<u8>,
+    chunk: usize,
+    capacity: usize,
+}
+
+impl Write for Limited {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let room = self.capacity - self.data.len();
+        if room == 0 {
+            return Err(io::Error::new(io::ErrorKind::WriteZero, "full"));
+        }
+        let n = buf.len().min(self.chunk).min(room);
+        self.data.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn copied_streams_match_one_shot_digests() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let data = contents(20_000);
+
+    let mut writer = HashWriter::new(f.init());
+    assert_eq!(io::copy(&mut Cursor::new(&data), &mut writer).unwrap(), 20_000);
+    assert_eq!(writer.finish(), f.digest(&data));
+
+    // the bytes are forwarded to the inner writer too
+    let mut writer = HashWriter::with_writer(f.init(), Vec::new());
+    io::copy(&mut Cursor::new(&data), &mut writer).unwrap();
+    let (ctx, copy) = writer.into_inner();
+    assert_eq!(copy, data);
+    assert_eq!(ctx.finish(), f.digest(&data));
+}
+
+#[test]
+fn only_accepted_bytes_are_hashed() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let data = contents(100);
+
+    // short writes are retried by `write_all()`
+    let inner = Limited { data: Vec::new(), chunk: 7, capacity: 1000 };
+    let mut writer = HashWriter::with_writer(f.init(), inner);
+    writer.write_all(&data).unwrap();
+    let (ctx, inner) = writer.into_inner();
+    assert_eq!(inner.data, data);
+    assert_eq!(ctx.finish(), f.digest(&data));
+
+    // a write failing halfway leaves the digest of what got through
+    let inner = Limited { data: Vec::new(), chunk: 7, capacity: 30 };
+    let mut writer = HashWriter::with_writer(f.init(), inner);
+    assert!(writer.write_all(&data).is_err());
+    let (ctx, inner) = writer.into_inner();
+    assert_eq!(inner.data, &data[..30]);
+    assert_eq!(ctx.finish(), f.digest(&data[..30]));
+}
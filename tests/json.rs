@@ -0,0 +1,31 @@
+use hash::json::digest_json_canonical;
+use hash::sip::SipHashFunction;
+use serde_json::{json, Value};
+
+fn digest(text: &str) -> u64 {
+    let value: Value = serde_json::from_str(text).unwrap();
+    digest_json_canonical(&value, &SipHashFunction::new_with_keys(1, 2))
+}
+
+#[test]
+fn key_order_does_not_matter() {
+    assert_eq!(digest(r#"{"a":1,"b":2}"#), digest(r#"{"b":2,"a":1}"#));
+    assert_eq!(digest(r#"{"x":{"a":[1,2],"b":null},"y":"s"}"#),
+               digest(r#"{"y":"s","x":{"b":null,"a":[1,2]}}"#));
+    assert_ne!(digest(r#"{"a":1,"b":2}"#), digest(r#"{"a":1}"#));
+}
+
+#[test]
+fn values_are_type_tagged() {
+    assert_ne!(digest("[1,2]"), digest("[2,1]"));
+    assert_ne!(digest("1"), digest("1.0"));
+    assert_ne!(digest("1"), digest(r#""1""#));
+    assert_ne!(digest("0"), digest("false"));
+    assert_ne!(digest("null"), digest("[]"));
+    assert_ne!(digest("[]"), digest("{}"));
+    assert_ne!(digest(r#"["ab","c"]"#), digest(r#"["a","bc"]"#));
+    assert_ne!(digest(r#"{"a":"b"}"#), digest(r#"["a","b"]"#));
+
+    let f = SipHashFunction::new_with_keys(1, 2);
+    assert_eq!(digest_json_canonical(&json!({"n": -3}), &f), digest(r#"{"n":-3}"#));
+}
@@ -0,0 +1,55 @@
+use hash::sip::SipHashFunction;
+use hash::traits::HashFunction;
+
+const K0: u64 = 0x0706050403020100;
+const K1: u64 = 0x0f0e0d0c0b0a0908;
+
+#[test]
+fn kmer_hashes_cover_every_window() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let seq = b"ACGTACGTTT";
+    let hashes = f.kmer_hashes(seq, 4);
+    assert_eq!(hashes.len(), seq.len() - 4 + 1);
+    for (i, &h) in hashes.iter().enumerate() {
+        assert_eq!(h, f.digest(&seq[i..i + 4]));
+    }
+    // "ACGT" occurs at 0 and 4
+    assert_eq!(hashes[0], hashes[4]);
+    assert_ne!(hashes[0], hashes[1]);
+
+    assert_eq!(f.kmer_hashes(seq, seq.len()), [f.digest(&seq[..])]);
+    assert!(f.kmer_hashes(seq, seq.len() + 1).is_empty());
+    assert!(f.kmer_hashes(b"", 1).is_empty());
+    assert!(f.kmer_hashes(seq, 0).is_empty());
+}
+
+#[test]
+fn minimizers_are_leftmost_window_minima() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let seq = b"ACGTTGCATGCAACGTAGGT";
+    let (k, w) = (3, 4);
+    let hashes = f.kmer_hashes(seq, k);
+    let minimizers = f.minimizers(seq, k, w);
+
+    // every window has its leftmost minimum among the minimizers
+    for start in 0..=hashes.len() - w {
+        let window = &hashes[start..start + w];
+        let min = *window.iter().min().unwrap();
+        let pos = start + window.iter().position(|&h| h == min).unwrap();
+        assert!(minimizers.contains(&(pos, min)), "window {}", start);
+    }
+    // sorted, without repeats, and a subset of the k-mer hashes
+    assert!(minimizers.windows(2).all(|m| m[0].0 < m[1].0));
+    for &(pos, hash) in &minimizers {
+        assert_eq!(hashes[pos], hash);
+    }
+
+    // ties: the repeated k-mer "AAA" is the minimum of every window, and
+    // the leftmost occurrence is selected until it leaves the window
+    let seq = b"AAAAAA";
+    let aaa = f.digest(b"AAA");
+    assert_eq!(f.minimizers(seq, 3, 2), [(0, aaa), (1, aaa), (2, aaa)]);
+
+    assert!(f.minimizers(seq, 3, 5).is_empty());
+    assert!(f.minimizers(seq, 3, 0).is_empty());
+}
@@ -0,0 +1,27 @@
+use hash::lsh::{hamming_distance, hamming_distance_bytes, simhash};
+use hash::sip::SipHashFunction;
+
+#[test]
+fn hamming_distances() {
+    assert_eq!(hamming_distance(0, 0), 0);
+    assert_eq!(hamming_distance(0b1011, 0b0110), 3);
+    assert_eq!(hamming_distance(0, u64::MAX), 64);
+    assert_eq!(hamming_distance_bytes(&[0xff, 0x00], &[0x0f, 0x01]), 5);
+    assert_eq!(hamming_distance_bytes(b"", b""), 0);
+}
+
+#[test]
+fn similar_feature_sets_get_close_simhashes() {
+    let f = SipHashFunction::new_with_keys(1, 2);
+    let words: Vec<String> = (0..100).map(|i| format!("word{}", i)).collect();
+    let features = |range: std::ops::Range<usize>| -> Vec<&[u8]> {
+        words[range].iter().map(|w| w.as_bytes()).collect()
+    };
+
+    let base = simhash(&features(0..50), &f);
+    let similar = simhash(&features(1..51), &f);
+    let different = simhash(&features(50..100), &f);
+    assert_eq!(simhash(&features(0..50), &f), base);
+    assert!(hamming_distance(base, similar) < 12, "{}", hamming_distance(base, similar));
+    assert!(hamming_distance(base, different) > 20, "{}", hamming_distance(base, different));
+}
@@ -0,0 +1,63 @@
+use hash::mac::MacSip;
+use hash::sip::{SipHash128Function, SipHashFunction};
+use hash::traits::{HashContext, HashFunction};
+
+const K0: u64 = 0x0706050403020100;
+const K1: u64 = 0x0f0e0d0c0b0a0908;
+
+/// Tag of `msg` padded to `min_len` bytes, as computed by `MacSip` with
+/// `(k0, k1)` as the padding key.
+fn padded_tag(k0: u64, k1: u64, msg: &[u8], min_len: usize) -> u64 {
+    let mut ctx = SipHashFunction::new_with_keys(k0, k1).init();
+    ctx.update(msg);
+    ctx.update(&vec![0; min_len - msg.len()]);
+    ctx.update(&(msg.len() as u64).to_le_bytes());
+    ctx.finish()
+}
+
+#[test]
+fn short_messages_are_padded() {
+    let mac = MacSip::new_with_keys(K0, K1, 16);
+    let f = SipHashFunction::new_with_keys(K0, K1);
+
+    assert_ne!(mac.digest(&[0]), mac.digest(&[0, 0]));
+    assert_ne!(mac.digest(b""), mac.digest(&[0]));
+    assert_ne!(mac.digest(b"short"), f.digest(b"short"));
+
+    // the padded encoding of a short message does not share its tag
+    let mut padded = [0u8; 24];
+    padded[0] = 1;
+    padded[16] = 1;
+    assert_ne!(mac.digest(&[1]), mac.digest(&padded));
+}
+
+#[test]
+fn long_messages_are_plain_siphash() {
+    let mac = MacSip::new_with_keys(K0, K1, 16);
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let msg = b"exactly sixteen!";
+    assert_eq!(mac.digest(msg), f.digest(msg));
+    assert_eq!(mac.digest(b"a longer message than that"), f.digest(b"a longer message than that"));
+    assert!(mac.verify(msg, mac.digest(msg)));
+    assert!(!mac.verify(b"exactly sixteen?", mac.digest(msg)));
+}
+
+#[test]
+fn tags_do_not_reveal_the_padding_key() {
+    let mac = MacSip::new_with_keys(K0, K1, 16);
+    let label: &[u8] = b"hash::mac padding";
+    let key = SipHash128Function::new_with_keys(K0, K1).digest(label);
+    assert_eq!(mac.digest(b"abc"), padded_tag(key as u64, (key >> 64) as u64, b"abc", 16));
+
+    // the tags of the labels (long enough to be plain SipHash) are not
+    // the padding key words
+    let labels: [&[u8]; 3] = [label, b"MacSip padding k0", b"MacSip padding k1"];
+    for &l in &labels {
+        assert_ne!(mac.digest(l), key as u64);
+        assert_ne!(mac.digest(l), (key >> 64) as u64);
+    }
+    let leaked = padded_tag(mac.digest(labels[1]), mac.digest(labels[2]), b"abc", 16);
+    assert_ne!(mac.digest(b"abc"), leaked);
+    let leaked = padded_tag(mac.digest(label), mac.digest(label), b"abc", 16);
+    assert_ne!(mac.digest(b"abc"), leaked);
+}
@@ -0,0 +1,21 @@
+use hash::multistream::MultiStreamHasher;
+use hash::sip::SipHashFunction;
+use hash::traits::HashFunction;
+
+#[test]
+fn interleaved_streams_are_digested_independently() {
+    let f = SipHashFunction::new_with_keys(1, 2);
+    let mut hasher = MultiStreamHasher::new(f);
+    hasher.feed(1, b"hello ");
+    hasher.feed(2, b"GET /");
+    hasher.feed(1, b"wor");
+    hasher.feed(2, b"index.html");
+    hasher.feed(1, b"ld");
+
+    assert_eq!(hasher.finish_stream(2), Some(f.digest(b"GET /index.html")));
+    assert_eq!(hasher.finish_stream(2), None);
+    hasher.feed(3, b"");
+    assert_eq!(hasher.finish_stream(1), Some(f.digest(b"hello world")));
+    assert_eq!(hasher.finish_stream(3), Some(f.digest(b"")));
+    assert_eq!(hasher.finish_stream(4), None);
+}
@@ -0,0 +1,99 @@
+use hash::murmur3::{Murmur3x128HashFunction, Murmur3x32HashFunction};
+use hash::traits::{HashContext, HashFunction};
+
+const SEED: u32 = 0x9747b28c;
+
+/// MurmurHash3_x86_32 with seed 0 and `SEED`, then MurmurHash3_x64_128
+/// with seed 0 and `SEED` (`h2` in the high bits), from the reference
+/// implementation.
+const STRINGS: [(&[u8], u32, u32, u128, u128); 4] = [
+    (b"", 0x00000000, 0xebb6c228,
+     0x00000000000000000000000000000000, 0x93b0608fe302957a392b208a1daabbb3),
+    (b"a", 0x3c2569b2, 0x7fa09ea6,
+     0xe6b53a48510e895a85555565f6597889, 0x9e6dab0f9208f0045ce8d8512db25a1d),
+    (b"abc", 0xb3dd93fa, 0xc84a62dd,
+     0x3ba2744126ca2d52b4963f3f3fad7867, 0xcde0a23420b504bf3743630dbfc3cedc),
+    (b"The quick brown fox jumps over the lazy dog", 0x2e4ff723, 0x2fa826cd,
+     0x7a433ca9c49a9347e34bbc7bbc071b6c, 0xf94573727ec016e5738a7f3bd2633121),
+];
+
+/// Same as `STRINGS`, for the first `len` bytes of `pattern()`.
+const LENGTHS: [(usize, u32, u32, u128, u128); 16] = [
+    (0, 0x00000000, 0xebb6c228,
+     0x00000000000000000000000000000000, 0x93b0608fe302957a392b208a1daabbb3),
+    (1, 0x514e28b7, 0x2933bea8,
+     0x51622daa78f835834610abe56eff5cb5, 0xf67103344e6f94c802c9f69a7dd730c0),
+    (3, 0xf05810c2, 0x033d0b24,
+     0x48508d588286a000d647a4d99fb946be, 0x180f1204936cdfd88e4c2b36e963b74b),
+    (4, 0x731c2886, 0xe7a89c53,
+     0x78a8a658edcddef86a798fcc7e410e0a, 0x6d2b613566711106dbcdfa9f2c101e89),
+    (7, 0xbf148eaf, 0x6b67dbf9,
+     0x5f5ad6ed354615390d082182b68ca733, 0xdd9702834d65a86e073ce4ba2047313f),
+    (8, 0x66c9b4c8, 0xddf9d7f4,
+     0x51b6262d2caf2f87b61aa2f86663eb95, 0x9b05f6762d635618bad59982d3b63dff),
+    (9, 0x080695bf, 0x104930dc,
+     0x4432e197690d6ae80444c582d3e116a9, 0x543dbd74efe3d240ab7d65d3c0e1b24f),
+    (15, 0xd66472e5, 0x2a46ea38,
+     0x346930385f768b164102da1094702f4b, 0x86115d5e8b42f97faa267f8df97050e6),
+    (16, 0x52ff2cad, 0xf33fe72d,
+     0xd1fbaa5136346fb45c4d0fff8376cf67, 0xdc41a4dd4152644e0f26e08252c03c6c),
+    (17, 0x6ce5e076, 0x0412a445,
+     0x5354d3157df44611eee2633553c88eb0, 0x0a7f795812f05d6edd9680f62caa97a7),
+    (128, 0x7b8b618a, 0x615d5c00,
+     0x8c2e04fab2d83a50534d4591c899bae3, 0x1d9b73f6811e47e1b65c92f8a6b1025c),
+    (129, 0x75d088db, 0x90d0f1f0,
+     0xa4e8c3a852362190e74b81a1a56ba617, 0x050e09828112b59f42f257015cae2d9a),
+    (240, 0x6f252335, 0x254531ad,
+     0x67a76013687e80a922976c4f878dc14f, 0x2e5c4ecb7652790de5dd4e3b21461b00),
+    (241, 0x59c4f8be, 0xa2bb6b08,
+     0x6fc8b1febbd11e7ce65328cbf348de72, 0x530c20f81cf015c0d8a2c2afc7948b0b),
+    (1024, 0xc464c3f9, 0xc0f0f364,
+     0x132047b1e29f315b3e33a05d0de49f1e, 0x5b1b9d9a06ac64ecb7bbb7ce68dd0541),
+    (5000, 0x760dda26, 0x1ff96808,
+     0x319e5d21c477af805c4d83aebc8f2531, 0xe44bdbc09b8a6e2230f9ebf7d3400b5b),
+];
+
+/// The bytes the length vectors are computed on.
+fn pattern(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 31 % 251) as u8).collect()
+}
+
+/// Feed `data` to `ctx` in `chunk`-byte updates.
+fn update_chunks<C: HashContext>(ctx: &mut C, data: &[u8], chunk: usize) {
+    for piece in data.chunks(chunk) {
+        ctx.update(piece);
+    }
+}
+
+/// Check `f` on `data` in one shot and split into `update()`s.
+fn check<F: HashFunction>(f: &F, data: &[u8], expected: <F::Context as HashContext>::Result)
+    where <F::Context as HashContext>::Result: PartialEq + std::fmt::Debug + Copy
+{
+    assert_eq!(f.digest(data), expected, "length {}", data.len());
+    for &chunk in &[1, 3, 16, 100] {
+        let mut ctx = f.init();
+        update_chunks(&mut ctx, data, chunk);
+        assert_eq!(ctx.finish(), expected, "length {}, chunks of {}", data.len(), chunk);
+    }
+}
+
+#[test]
+fn reference_vectors() {
+    let x32 = Murmur3x32HashFunction::new();
+    let x32_seeded = Murmur3x32HashFunction::new_with_seed(SEED);
+    let x128 = Murmur3x128HashFunction::new();
+    let x128_seeded = Murmur3x128HashFunction::new_with_seed(SEED);
+    for &(msg, a, b, c, d) in &STRINGS {
+        check(&x32, msg, a);
+        check(&x32_seeded, msg, b);
+        check(&x128, msg, c);
+        check(&x128_seeded, msg, d);
+    }
+    let data = pattern(5000);
+    for &(len, a, b, c, d) in &LENGTHS {
+        check(&x32, &data[..len], a);
+        check(&x32_seeded, &data[..len], b);
+        check(&x128, &data[..len], c);
+        check(&x128_seeded, &data[..len], d);
+    }
+}
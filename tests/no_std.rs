@@ -0,0 +1,25 @@
+//! Only uses what the crate provides without the `std` feature, i.e. in
+//! the default `no_std` build. Tests get `std` through the dev-dependency
+//! of the crate on itself, so the `no_std` build is checked by building
+//! the library, this only covers its API.
+
+use hash::sip::{SipContext, SipHashFunction};
+use hash::traits::{Hash, HashContext, HashFunction};
+
+const K0: u64 = 0x0706050403020100;
+const K1: u64 = 0x0f0e0d0c0b0a0908;
+
+#[test]
+fn digests_without_std() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    // SipHash-2-4 reference vectors of length 0 and 8
+    assert_eq!(f.digest(&[]), 0x726fdb47dd0e0e31);
+    assert_eq!(f.digest(&[0, 1, 2, 3, 4, 5, 6, 7]), 0x93f5f5799a932462);
+
+    let mut ctx: SipContext = f.init();
+    ctx.update(&[0, 1, 2]);
+    ctx.update(&[3, 4, 5, 6, 7]);
+    assert_eq!(ctx.finish(), 0x93f5f5799a932462);
+
+    assert_eq!(0x0706050403020100u64.digest(&f), 0x93f5f5799a932462);
+}
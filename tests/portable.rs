@@ -0,0 +1,76 @@
+use hash::traits::{Hash, HashContext};
+use proptest::prelude::*;
+
+/// Context recording the bytes it is fed, to check encodings.
+struct Bytes(Vec<u8>);
+
+impl HashContext for Bytes {
+    type Result = Vec<u8>;
+
+    fn update(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes)
+    }
+
+    fn finish(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+fn encode<T: Hash + ?Sized>(value: &T) -> Vec<u8> {
+    let mut ctx = Bytes(Vec::new());
+    value.hash(&mut ctx);
+    ctx.finish()
+}
+
+// The expected encodings are spelled out byte by byte, so these hold on
+// big-endian and 32-bit targets as well.
+#[test]
+fn primitive_encodings() {
+    assert_eq!(encode(&0x0102u16), [0x02, 0x01]);
+    assert_eq!(encode(&0x01020304u32), [0x04, 0x03, 0x02, 0x01]);
+    assert_eq!(encode(&-2i32), [0xfe, 0xff, 0xff, 0xff]);
+    assert_eq!(encode(&1usize), [1, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(encode(&-1isize), [0xff; 8]);
+    assert_eq!(encode(&'A'), [0x41, 0, 0, 0]);
+    assert_eq!(encode(&true), [1]);
+    assert_eq!(encode(&1.0f64), [0, 0, 0, 0, 0, 0, 0xf0, 0x3f]);
+    assert_eq!(encode(&-0.0f32), encode(&0.0f32));
+    assert_eq!(encode(&f64::NAN), encode(&-f64::NAN));
+}
+
+#[test]
+fn composite_encodings() {
+    assert_eq!(encode("ab"), [2, 0, 0, 0, 0, 0, 0, 0, b'a', b'b']);
+    assert_eq!(encode(&[1u8, 2]), [1, 2]);
+    assert_eq!(encode(&(1u8, 2u16)), [1, 2, 0]);
+    assert_eq!(encode(&None::<u8>), [0]);
+    assert_eq!(encode(&Some(7u8)), [1, 7]);
+    assert_eq!(encode(&Err::<u8, u8>(7)), [1, 7]);
+}
+
+#[test]
+fn explicit_byte_orders() {
+    let mut le = Bytes(Vec::new());
+    le.update_u64_le(0x0102030405060708);
+    assert_eq!(le.finish(), [8, 7, 6, 5, 4, 3, 2, 1]);
+
+    let mut be = Bytes(Vec::new());
+    be.update_u64_be(0x0102030405060708);
+    assert_eq!(be.finish(), [1, 2, 3, 4, 5, 6, 7, 8]);
+}
+
+proptest! {
+    #[test]
+    fn integers_are_little_endian(a: u16, b: u32, c: u64, d: u128) {
+        prop_assert_eq!(encode(&a), a.to_le_bytes());
+        prop_assert_eq!(encode(&b), b.to_le_bytes());
+        prop_assert_eq!(encode(&c), c.to_le_bytes());
+        prop_assert_eq!(encode(&d), d.to_le_bytes());
+    }
+
+    #[test]
+    fn string_pairs_are_prefix_free(a: String, b: String, c: String, d: String) {
+        prop_assume!((&a, &b) != (&c, &d));
+        prop_assert_ne!(encode(&(&a, &b)), encode(&(&c, &d)));
+    }
+}
@@ -0,0 +1,36 @@
+use hash::ring::HashRing;
+
+const K0: u64 = 0x0706050403020100;
+const K1: u64 = 0x0f0e0d0c0b0a0908;
+
+#[test]
+fn weighted_nodes_own_proportional_shares() {
+    let mut ring = HashRing::new_with_keys(K0, K1);
+    ring.add_node_weighted("small", 200);
+    ring.add_node_weighted("large", 400);
+
+    let mut large = 0;
+    for i in 0..30_000u32 {
+        let key = i.to_le_bytes();
+        let node = *ring.get_node(&key).unwrap();
+        assert_eq!(*ring.get_node(&key).unwrap(), node);
+        if node == "large" {
+            large += 1;
+        }
+    }
+    // about two thirds of the keys
+    assert!((18_500..21_500).contains(&large), "{}", large);
+
+    // another ring with the same nodes routes identically
+    let mut other = HashRing::new_with_keys(K0, K1);
+    other.add_node_weighted("large", 400);
+    other.add_node_weighted("small", 200);
+    for i in 0..1000u32 {
+        assert_eq!(ring.get_node(&i.to_le_bytes()), other.get_node(&i.to_le_bytes()));
+    }
+
+    ring.remove_node(&"large");
+    assert_eq!(ring.get_node(b"key"), Some(&"small"));
+    ring.remove_node(&"small");
+    assert_eq!(ring.get_node(b"key"), None);
+}
@@ -0,0 +1,43 @@
+use hash::fnv::FnvHashFunction;
+use hash::rng::{seed_from_hash, std_rng_from_hash};
+use hash::sip::SipHashFunction;
+use hash::traits::HashFunction;
+use rand::RngCore;
+
+#[test]
+fn seeds_are_deterministic_per_label() {
+    let f = SipHashFunction::new_with_keys(7, 39);
+    assert_eq!(seed_from_hash(&f, b"shuffle"), seed_from_hash(&f, b"shuffle"));
+    assert_ne!(seed_from_hash(&f, b"shuffle"), seed_from_hash(&f, b"sample"));
+    assert_ne!(seed_from_hash(&f, b"shuffle"),
+               seed_from_hash(&SipHashFunction::new_with_keys(7, 40), b"shuffle"));
+
+    // the four words are domain-separated
+    let seed = seed_from_hash(&f, b"shuffle");
+    for i in 0..4 {
+        for j in i + 1..4 {
+            assert_ne!(seed[8 * i..8 * i + 8], seed[8 * j..8 * j + 8]);
+        }
+    }
+
+    // the words are not the digests of caller data starting with the
+    // word index
+    for i in 0..4u8 {
+        let mut data = vec![i];
+        data.extend_from_slice(b"shuffle");
+        assert!(seed.chunks(8).all(|w| w != f.digest(&data).to_le_bytes()));
+    }
+}
+
+#[test]
+fn rngs_replay_the_same_stream() {
+    let f = FnvHashFunction::new();
+    let mut a = std_rng_from_hash(&f, b"fixture");
+    let mut b = std_rng_from_hash(&f, b"fixture");
+    let mut c = std_rng_from_hash(&f, b"other fixture");
+    for _ in 0..16 {
+        let x = a.next_u64();
+        assert_eq!(x, b.next_u64());
+        assert_ne!(x, c.next_u64());
+    }
+}
@@ -0,0 +1,102 @@
+use std::io::{self, Read};
+
+use hash::rolling::{rolling_boundaries, BuzHash, RollingHash, DEFAULT_WINDOW};
+use hash::traits::HashFunction;
+use proptest::prelude::*;
+
+/// Reader returning at most `step` bytes per call, with an interruption
+/// before each of them.
+struct Trickle<'a> {
+    data: &'a [u8],
+    step: usize,
+    interrupted: bool,
+}
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.interrupted = !self.interrupted;
+        if self.interrupted {
+            return Err(io::ErrorKind::Interrupted.into());
+        }
+        let n = self.step.min(buf.len()).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(n)
+    }
+}
+
+#[test]
+fn streamed_boundaries_match_in_memory() {
+    let mut state = 0x1234_5678u32;
+    let data: Vec<u8> = (0..100_000).map(|_| {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state as u8
+    }).collect();
+
+    let expected = BuzHash::new(DEFAULT_WINDOW).boundaries(&data, 1023);
+    assert!(expected.len() > 50, "{}", expected.len());
+    assert_eq!(rolling_boundaries(&data[..], 1000).unwrap(), expected);
+    assert_eq!(rolling_boundaries(&data[..], 1024).unwrap(), expected);
+
+    let trickle = Trickle { data: &data, step: 777, interrupted: false };
+    assert_eq!(rolling_boundaries(trickle, 1024).unwrap(), expected);
+
+    assert!(rolling_boundaries(&data[..DEFAULT_WINDOW - 1], 1).unwrap().is_empty());
+}
+
+proptest! {
+    #[test]
+    fn slide_matches_digest_of_window(
+        window in 1usize..80,
+        data in proptest::collection::vec(any::<u8>(), 0..400),
+    ) {
+        let f = BuzHash::new(window);
+        let mut ctx = f.init();
+        for (i, &b) in data.iter().enumerate() {
+            if i < window {
+                ctx.push(b);
+            } else {
+                ctx.slide(data[i - window], b);
+            }
+            let start = (i + 1).saturating_sub(window);
+            prop_assert_eq!(ctx.value(), f.digest(&data[start..=i]));
+        }
+    }
+
+    #[test]
+    fn boundaries_are_invariant_under_shifts(
+        window in 1usize..64,
+        prefix in proptest::collection::vec(any::<u8>(), 0..64),
+        data in proptest::collection::vec(any::<u8>(), 0..4096),
+    ) {
+        let f = BuzHash::new(window);
+        let mut shifted = prefix.clone();
+        shifted.extend_from_slice(&data);
+
+        // cut points past the first full window of `data` only depend on
+        // `data`, so they move by the length of the prefix
+        let cuts: Vec<usize> = f.boundaries(&data, 0x1f).into_iter()
+            .filter(|&c| c >= window)
+            .collect();
+        let shifted_cuts: Vec<usize> = f.boundaries(&shifted, 0x1f).into_iter()
+            .filter(|&c| c >= prefix.len() + window)
+            .map(|c| c - prefix.len())
+            .collect();
+        prop_assert_eq!(cuts, shifted_cuts);
+    }
+}
+
+proptest! {
+    #[test]
+    fn read_boundaries_match_boundaries(
+        window in 1usize..64,
+        step in 1usize..100,
+        data in proptest::collection::vec(any::<u8>(), 0..4096),
+    ) {
+        let f = BuzHash::new(window);
+        let trickle = Trickle { data: &data, step, interrupted: false };
+        prop_assert_eq!(f.read_boundaries(trickle, 0x1f).unwrap(), f.boundaries(&data, 0x1f));
+    }
+}
@@ -0,0 +1,93 @@
+use hash::sip::SipHashFunction;
+use hash::traits::HashFunction;
+
+const K0: u64 = 0x0706050403020100;
+const K1: u64 = 0x0f0e0d0c0b0a0908;
+
+#[test]
+fn unit_fractions_are_uniform() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let mut deciles = [0u32; 10];
+    for i in 0..100_000u32 {
+        let u = f.digest_unit_f64(&i.to_le_bytes());
+        assert!((0.0..1.0).contains(&u), "{}", u);
+        deciles[(u * 10.0) as usize] += 1;
+    }
+    for &count in &deciles {
+        assert!((9_500..10_500).contains(&count), "{:?}", deciles);
+    }
+
+    assert_eq!(f.digest_unit_f64(b"trace-id"), f.digest_unit_f64(b"trace-id"));
+    assert_ne!(f.digest_unit_f64(b"trace-id"), f.digest_unit_f64(b"trace-ie"));
+}
+
+#[test]
+fn heavier_items_get_larger_reservoir_keys() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let mean = |weight: f64| {
+        (0..10_000u32).map(|i| f.reservoir_key(&i.to_le_bytes(), weight)).sum::<f64>() / 10_000.0
+    };
+    // the mean key of weight w is w / (w + 1)
+    assert!((mean(1.0) - 0.5).abs() < 0.02);
+    assert!((mean(9.0) - 0.9).abs() < 0.02);
+
+    // the same item always gets a larger key with a larger weight
+    for i in 0..1000u32 {
+        let item = i.to_le_bytes();
+        assert!(f.reservoir_key(&item, 2.0) >= f.reservoir_key(&item, 1.0));
+        assert_eq!(f.reservoir_key(&item, 2.0), f.reservoir_key(&item, 2.0));
+    }
+}
+
+#[test]
+fn jitter_stays_in_the_backoff_window() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    for attempt in 0..10 {
+        let cap = 100u64 << attempt;
+        let mut min = u64::MAX;
+        let mut max = 0;
+        for client in 0..1000u32 {
+            let key = client.to_le_bytes();
+            let delay = f.jitter(&key, 100, attempt);
+            assert!((cap / 2..cap).contains(&delay), "{} {}", attempt, delay);
+            assert_eq!(f.jitter(&key, 100, attempt), delay);
+            min = min.min(delay);
+            max = max.max(delay);
+        }
+        // the clients spread across the whole window
+        assert!(min < cap / 2 + cap / 20 && max > cap - cap / 20, "{} {} {}", attempt, min, max);
+    }
+
+    // the cap saturates instead of overflowing
+    let delay = f.jitter(b"client", 100, 200);
+    assert!(delay >= u64::MAX / 2);
+}
+
+#[test]
+fn train_test_splits_are_stable() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let (train, test) = f.train_test_split(10_000, 0.2);
+    assert_eq!(f.train_test_split(10_000, 0.2), (train.clone(), test.clone()));
+    assert_eq!(train.len() + test.len(), 10_000);
+    assert!((1_800..2_200).contains(&test.len()), "{}", test.len());
+    let mut all: Vec<usize> = train.iter().chain(&test).cloned().collect();
+    all.sort_unstable();
+    assert!(all.iter().enumerate().all(|(i, &j)| i == j));
+
+    // appending samples keeps the previous assignments
+    let (more_train, more_test) = f.train_test_split(12_000, 0.2);
+    assert_eq!(more_train[..train.len()], train[..]);
+    assert_eq!(more_test[..test.len()], test[..]);
+    assert!(more_train[train.len()..].iter().chain(&more_test[test.len()..]).all(|&i| i >= 10_000));
+
+    assert!(f.train_test_split(100, 0.0).1.is_empty());
+    assert!(f.train_test_split(100, 1.0).0.is_empty());
+
+    // the split has its own domain, instead of following the plain
+    // digests of the 8-byte indices
+    let unit = |h: u64| (h >> 11) as f64 / (1u64 << 53) as f64;
+    let plain: Vec<usize> = (0..10_000usize)
+        .filter(|&i| unit(f.digest(&(i as u64).to_le_bytes())) < 0.2)
+        .collect();
+    assert_ne!(test, plain);
+}
@@ -0,0 +1,72 @@
+use hash::seed::{sub_seed, FromHashSeed};
+use hash::sip::SipHashFunction;
+use hash::traits::{HashContext, HashFunction};
+use hash::util::derive_seed;
+
+/// A fixture built from the seeds of its fields, like the tuple impls.
+#[derive(Debug, PartialEq)]
+struct User {
+    id: u64,
+    score: f64,
+    active: bool,
+}
+
+impl FromHashSeed for User {
+    fn from_hash_seed(seed: u64, f: &SipHashFunction) -> User {
+        User {
+            id: u64::from_hash_seed(sub_seed(seed, 0, f), f),
+            score: f64::from_hash_seed(sub_seed(seed, 1, f), f),
+            active: bool::from_hash_seed(sub_seed(seed, 2, f), f),
+        }
+    }
+}
+
+#[test]
+fn same_seed_same_value() {
+    let f = SipHashFunction::new_with_keys(1, 2);
+    assert_eq!(u64::from_hash_seed(7, &f), u64::from_hash_seed(7, &f));
+    assert_ne!(u64::from_hash_seed(7, &f), u64::from_hash_seed(8, &f));
+    assert_ne!(u128::from_hash_seed(7, &f), u128::from_hash_seed(8, &f));
+    assert_ne!(<(u32, i16, [u8; 4])>::from_hash_seed(7, &f),
+               <(u32, i16, [u8; 4])>::from_hash_seed(8, &f));
+    assert_eq!(User::from_hash_seed(7, &f), User::from_hash_seed(7, &f));
+    assert_ne!(User::from_hash_seed(7, &f), User::from_hash_seed(8, &f));
+    assert_ne!(u64::from_hash_seed(7, &f), u64::from_hash_seed(7, &SipHashFunction::new_with_keys(1, 3)));
+}
+
+#[test]
+fn components_are_independent() {
+    let f = SipHashFunction::new_with_keys(1, 2);
+    let (a, b) = <(u64, u64)>::from_hash_seed(7, &f);
+    assert_ne!(a, b);
+    let words = <[u64; 8]>::from_hash_seed(7, &f);
+    for (i, word) in words.iter().enumerate() {
+        assert!(!words[..i].contains(word));
+    }
+
+    let trues = (0..1000).filter(|&seed| bool::from_hash_seed(seed, &f)).count();
+    assert!((400..600).contains(&trues), "{}", trues);
+    for seed in 0..1000 {
+        let x = f64::from_hash_seed(seed, &f);
+        let y = f32::from_hash_seed(seed, &f);
+        assert!((0.0..1.0).contains(&x) && (0.0..1.0).contains(&y));
+    }
+}
+
+#[test]
+fn derived_keys_are_labelled() {
+    let f = SipHashFunction::new_with_keys(7, 39);
+    let key = derive_seed(&f, 5).to_bytes();
+    assert_eq!(derive_seed(&f, 5).to_bytes(), key);
+    assert_ne!(derive_seed(&f, 6).to_bytes(), key);
+    assert_ne!(derive_seed(&SipHashFunction::new_with_keys(7, 40), 5).to_bytes(), key);
+
+    // the key words are not the digests of the unlabelled index and tag
+    for i in 0..2u8 {
+        let mut ctx = f.init();
+        ctx.write_u8(i);
+        ctx.write_u64(5);
+        let word = ctx.finish().to_le_bytes();
+        assert!(key.chunks(8).all(|k| k != word));
+    }
+}
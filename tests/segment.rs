@@ -0,0 +1,29 @@
+use hash::segment::SegmentHasher;
+use hash::sip::SipHashFunction;
+use hash::traits::HashFunction;
+
+#[test]
+fn segments_are_digested_independently() {
+    let f = SipHashFunction::new_with_keys(1, 2);
+    let data: Vec<u8> = (0..250u32).map(|i| i as u8).collect();
+
+    // feed two and a half segments of 100 bytes in uneven pieces
+    let mut hasher = SegmentHasher::new(f, 100);
+    let mut emitted = Vec::new();
+    for piece in data.chunks(33) {
+        hasher.feed(piece, |i, digest| emitted.push((i, digest)));
+    }
+
+    assert_eq!(emitted, [(0, f.digest(&data[..100])), (1, f.digest(&data[100..200]))]);
+    assert_eq!(hasher.finish(), Some((2, f.digest(&data[200..]))));
+}
+
+#[test]
+fn no_partial_segment_after_a_boundary() {
+    let f = SipHashFunction::new_with_keys(1, 2);
+    let mut hasher = SegmentHasher::new(f, 4);
+    let mut count = 0;
+    hasher.feed(b"abcdefgh", |_, _| count += 1);
+    assert_eq!(count, 2);
+    assert_eq!(hasher.finish(), None);
+}
@@ -0,0 +1,36 @@
+use hash::selective::SelectiveHasher;
+use hash::sip::SipHashFunction;
+use hash::traits::Hash;
+
+struct Document {
+    id: u64,
+    title: String,
+    modified_at: u64,
+}
+
+fn digest(doc: &Document, f: &SipHashFunction) -> u64 {
+    SelectiveHasher::new(f)
+        .field(&doc.id)
+        .field(&doc.title)
+        .skip(&doc.modified_at)
+        .finish()
+}
+
+#[test]
+fn excluded_fields_do_not_contribute() {
+    let f = SipHashFunction::new_with_keys(1, 2);
+    let a = Document { id: 1, title: "draft".to_string(), modified_at: 100 };
+    let b = Document { id: 1, title: "draft".to_string(), modified_at: 200 };
+    let c = Document { id: 1, title: "final".to_string(), modified_at: 100 };
+    assert_eq!(digest(&a, &f), digest(&b, &f));
+    assert_ne!(digest(&a, &f), digest(&c, &f));
+
+    // the same as a value without the excluded field
+    assert_eq!(digest(&a, &f), (1u64, "draft").digest(&f));
+
+    let conditional = |include: bool| {
+        SelectiveHasher::new(&f).field(&a.id).field_if(include, &a.modified_at).finish()
+    };
+    assert_eq!(conditional(false), 1u64.digest(&f));
+    assert_eq!(conditional(true), (1u64, 100u64).digest(&f));
+}
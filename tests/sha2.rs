@@ -0,0 +1,64 @@
+use hash::sha2::Sha256HashFunction;
+use hash::traits::{HashContext, HashFunction};
+
+/// SHA-256 test vectors from FIPS 180-2.
+const STRINGS: [(&[u8], &str); 3] = [
+    (b"abc", "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+    (b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+     "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"),
+    (b"", "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"),
+];
+
+/// Same as `STRINGS`, for the first `len` bytes of `pattern()`.
+const LENGTHS: [(usize, &str); 16] = [
+    (0, "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"),
+    (1, "6e340b9cffb37a989ca544e6bb780a2c78901d3fb33738768511a30617afa01d"),
+    (3, "94e1c77d23e949c5fe5b5309709054b595ae0ddd29b86b0b4f89ba8a7de870fe"),
+    (4, "0828fbe8068521d9003508f0b4015c9c2d3d86008a49a8ff12c1677104837871"),
+    (7, "7a57226673a2a6903c169e2aa557ab86630054d6c6facb2c7d688e29ead11c87"),
+    (8, "6ef44432d97e0a4e62aaef2ea2b3b79c6371347c2ce08f26f8eb5e735c18aa47"),
+    (9, "5506a5093851d7659780bfb58d230876a7c695d50995d631f47cce65a2f375a8"),
+    (15, "262d9eed95328a8d8d65ec56089207c09af567b0718be5f510c62b337b4c5b77"),
+    (16, "f59df330e85ca168788a07ee335883dc6f6cc158a7e86ef5672d3a2c2f666121"),
+    (17, "3bc522c4d10a33bfdeed285c37f13a9eebbf541de68de732651663f15f491a1d"),
+    (128, "eaaa24524227b908e482a9ee3348eccbfab2e552f8859b69f8dc2ce2358651b1"),
+    (129, "ce6b67fbe91b853dc29c9c6b912c06c32b807f029ec422b0b0ef4e3eed6aec58"),
+    (240, "7060538b55e01156080dace36f00d6ccdfde84abb91581b67aade92fadea3eaf"),
+    (241, "dd53d2dd007201f8ae8d5896871b98ed5802de08d61b67ba70aaa27911695ced"),
+    (1024, "566831246a14668f33e86d5501f4fcc66b10d28b0ab3e0727970520da68d9de4"),
+    (5000, "65373ec98993982941a115c7c7d7f43e5dfb0a118e80ea769c5126509f6a6806"),
+];
+
+/// The bytes the length vectors are computed on.
+fn pattern(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 31 % 251) as u8).collect()
+}
+
+/// Feed `data` to `ctx` in `chunk`-byte updates.
+fn update_chunks<C: HashContext>(ctx: &mut C, data: &[u8], chunk: usize) {
+    for piece in data.chunks(chunk) {
+        ctx.update(piece);
+    }
+}
+
+fn hex(digest: [u8; 32]) -> String {
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[test]
+fn reference_vectors() {
+    let f = Sha256HashFunction::new();
+    for &(msg, expected) in &STRINGS {
+        assert_eq!(hex(f.digest(msg)), expected, "{:?}", msg);
+    }
+    let data = pattern(5000);
+    for &(len, expected) in &LENGTHS {
+        assert_eq!(hex(f.digest(&data[..len])), expected, "length {}", len);
+        // around the 64-byte block and the 56-byte padding limit
+        for &chunk in &[1, 55, 64, 100] {
+            let mut ctx = f.init();
+            update_chunks(&mut ctx, &data[..len], chunk);
+            assert_eq!(hex(ctx.finish()), expected, "length {}, chunks of {}", len, chunk);
+        }
+    }
+}
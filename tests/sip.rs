@@ -0,0 +1,708 @@
+use hash::sip::{HashKey, Key, Sip13HashFunction, SipHash128Function, SipHashFunction,
+                SipOutput, SipVariable};
+use hash::traits::{Hash, HashContext, HashFunction, ResettableContext};
+use proptest::prelude::*;
+
+/// Output of SipHash-2-4 with the key `00 01 .. 0f` for the messages
+/// `[]`, `[00]`, `[00 01]`, .. `[00 .. 3e]`, from the reference
+/// implementation (as little-endian bytes).
+const VECTORS: [[u8; 8]; 64] = [
+    [0x31, 0x0e, 0x0e, 0xdd, 0x47, 0xdb, 0x6f, 0x72],
+    [0xfd, 0x67, 0xdc, 0x93, 0xc5, 0x39, 0xf8, 0x74],
+    [0x5a, 0x4f, 0xa9, 0xd9, 0x09, 0x80, 0x6c, 0x0d],
+    [0x2d, 0x7e, 0xfb, 0xd7, 0x96, 0x66, 0x67, 0x85],
+    [0xb7, 0x87, 0x71, 0x27, 0xe0, 0x94, 0x27, 0xcf],
+    [0x8d, 0xa6, 0x99, 0xcd, 0x64, 0x55, 0x76, 0x18],
+    [0xce, 0xe3, 0xfe, 0x58, 0x6e, 0x46, 0xc9, 0xcb],
+    [0x37, 0xd1, 0x01, 0x8b, 0xf5, 0x00, 0x02, 0xab],
+    [0x62, 0x24, 0x93, 0x9a, 0x79, 0xf5, 0xf5, 0x93],
+    [0xb0, 0xe4, 0xa9, 0x0b, 0xdf, 0x82, 0x00, 0x9e],
+    [0xf3, 0xb9, 0xdd, 0x94, 0xc5, 0xbb, 0x5d, 0x7a],
+    [0xa7, 0xad, 0x6b, 0x22, 0x46, 0x2f, 0xb3, 0xf4],
+    [0xfb, 0xe5, 0x0e, 0x86, 0xbc, 0x8f, 0x1e, 0x75],
+    [0x90, 0x3d, 0x84, 0xc0, 0x27, 0x56, 0xea, 0x14],
+    [0xee, 0xf2, 0x7a, 0x8e, 0x90, 0xca, 0x23, 0xf7],
+    [0xe5, 0x45, 0xbe, 0x49, 0x61, 0xca, 0x29, 0xa1],
+    [0xdb, 0x9b, 0xc2, 0x57, 0x7f, 0xcc, 0x2a, 0x3f],
+    [0x94, 0x47, 0xbe, 0x2c, 0xf5, 0xe9, 0x9a, 0x69],
+    [0x9c, 0xd3, 0x8d, 0x96, 0xf0, 0xb3, 0xc1, 0x4b],
+    [0xbd, 0x61, 0x79, 0xa7, 0x1d, 0xc9, 0x6d, 0xbb],
+    [0x98, 0xee, 0xa2, 0x1a, 0xf2, 0x5c, 0xd6, 0xbe],
+    [0xc7, 0x67, 0x3b, 0x2e, 0xb0, 0xcb, 0xf2, 0xd0],
+    [0x88, 0x3e, 0xa3, 0xe3, 0x95, 0x67, 0x53, 0x93],
+    [0xc8, 0xce, 0x5c, 0xcd, 0x8c, 0x03, 0x0c, 0xa8],
+    [0x94, 0xaf, 0x49, 0xf6, 0xc6, 0x50, 0xad, 0xb8],
+    [0xea, 0xb8, 0x85, 0x8a, 0xde, 0x92, 0xe1, 0xbc],
+    [0xf3, 0x15, 0xbb, 0x5b, 0xb8, 0x35, 0xd8, 0x17],
+    [0xad, 0xcf, 0x6b, 0x07, 0x63, 0x61, 0x2e, 0x2f],
+    [0xa5, 0xc9, 0x1d, 0xa7, 0xac, 0xaa, 0x4d, 0xde],
+    [0x71, 0x65, 0x95, 0x87, 0x66, 0x50, 0xa2, 0xa6],
+    [0x28, 0xef, 0x49, 0x5c, 0x53, 0xa3, 0x87, 0xad],
+    [0x42, 0xc3, 0x41, 0xd8, 0xfa, 0x92, 0xd8, 0x32],
+    [0xce, 0x7c, 0xf2, 0x72, 0x2f, 0x51, 0x27, 0x71],
+    [0xe3, 0x78, 0x59, 0xf9, 0x46, 0x23, 0xf3, 0xa7],
+    [0x38, 0x12, 0x05, 0xbb, 0x1a, 0xb0, 0xe0, 0x12],
+    [0xae, 0x97, 0xa1, 0x0f, 0xd4, 0x34, 0xe0, 0x15],
+    [0xb4, 0xa3, 0x15, 0x08, 0xbe, 0xff, 0x4d, 0x31],
+    [0x81, 0x39, 0x62, 0x29, 0xf0, 0x90, 0x79, 0x02],
+    [0x4d, 0x0c, 0xf4, 0x9e, 0xe5, 0xd4, 0xdc, 0xca],
+    [0x5c, 0x73, 0x33, 0x6a, 0x76, 0xd8, 0xbf, 0x9a],
+    [0xd0, 0xa7, 0x04, 0x53, 0x6b, 0xa9, 0x3e, 0x0e],
+    [0x92, 0x59, 0x58, 0xfc, 0xd6, 0x42, 0x0c, 0xad],
+    [0xa9, 0x15, 0xc2, 0x9b, 0xc8, 0x06, 0x73, 0x18],
+    [0x95, 0x2b, 0x79, 0xf3, 0xbc, 0x0a, 0xa6, 0xd4],
+    [0xf2, 0x1d, 0xf2, 0xe4, 0x1d, 0x45, 0x35, 0xf9],
+    [0x87, 0x57, 0x75, 0x19, 0x04, 0x8f, 0x53, 0xa9],
+    [0x10, 0xa5, 0x6c, 0xf5, 0xdf, 0xcd, 0x9a, 0xdb],
+    [0xeb, 0x75, 0x09, 0x5c, 0xcd, 0x98, 0x6c, 0xd0],
+    [0x51, 0xa9, 0xcb, 0x9e, 0xcb, 0xa3, 0x12, 0xe6],
+    [0x96, 0xaf, 0xad, 0xfc, 0x2c, 0xe6, 0x66, 0xc7],
+    [0x72, 0xfe, 0x52, 0x97, 0x5a, 0x43, 0x64, 0xee],
+    [0x5a, 0x16, 0x45, 0xb2, 0x76, 0xd5, 0x92, 0xa1],
+    [0xb2, 0x74, 0xcb, 0x8e, 0xbf, 0x87, 0x87, 0x0a],
+    [0x6f, 0x9b, 0xb4, 0x20, 0x3d, 0xe7, 0xb3, 0x81],
+    [0xea, 0xec, 0xb2, 0xa3, 0x0b, 0x22, 0xa8, 0x7f],
+    [0x99, 0x24, 0xa4, 0x3c, 0xc1, 0x31, 0x57, 0x24],
+    [0xbd, 0x83, 0x8d, 0x3a, 0xaf, 0xbf, 0x8d, 0xb7],
+    [0x0b, 0x1a, 0x2a, 0x32, 0x65, 0xd5, 0x1a, 0xea],
+    [0x13, 0x50, 0x79, 0xa3, 0x23, 0x1c, 0xe6, 0x60],
+    [0x93, 0x2b, 0x28, 0x46, 0xe4, 0xd7, 0x06, 0x66],
+    [0xe1, 0x91, 0x5f, 0x5c, 0xb1, 0xec, 0xa4, 0x6c],
+    [0xf3, 0x25, 0x96, 0x5c, 0xa1, 0x6d, 0x62, 0x9f],
+    [0x57, 0x5f, 0xf2, 0x8e, 0x60, 0x38, 0x1b, 0xe5],
+    [0x72, 0x45, 0x06, 0xeb, 0x4c, 0x32, 0x8a, 0x95],
+];
+
+/// Same as `VECTORS`, for SipHash-1-3.
+const VECTORS_13: [[u8; 8]; 64] = [
+    [0xdc, 0xc4, 0x0f, 0x05, 0x58, 0x01, 0xac, 0xab],
+    [0x93, 0xca, 0x57, 0x7d, 0xf3, 0x9b, 0xf4, 0xc9],
+    [0x4d, 0xd4, 0xc7, 0x4d, 0x02, 0x9b, 0xcb, 0x82],
+    [0xfb, 0xf7, 0xdd, 0xe7, 0xb8, 0x0a, 0xf8, 0x8b],
+    [0x28, 0x83, 0xd3, 0x88, 0x60, 0x57, 0x75, 0xcf],
+    [0x67, 0x3b, 0x53, 0x49, 0x2f, 0xd5, 0xf9, 0xde],
+    [0xa7, 0x22, 0x9f, 0xc5, 0x50, 0x2b, 0x0d, 0xc5],
+    [0x40, 0x11, 0xb1, 0x9b, 0x98, 0x7d, 0x92, 0xd3],
+    [0x8e, 0x9a, 0x29, 0x8d, 0x11, 0x95, 0x90, 0x36],
+    [0xe4, 0x3d, 0x06, 0x6c, 0xb3, 0x8e, 0xa4, 0x25],
+    [0x7f, 0x09, 0xff, 0x92, 0xee, 0x85, 0xde, 0x79],
+    [0x52, 0xc3, 0x4d, 0xf9, 0xc1, 0x18, 0xc1, 0x70],
+    [0xa2, 0xd9, 0xb4, 0x57, 0xb1, 0x84, 0xa3, 0x78],
+    [0xa7, 0xff, 0x29, 0x12, 0x0c, 0x76, 0x6f, 0x30],
+    [0x34, 0x5d, 0xf9, 0xc0, 0x11, 0xa1, 0x5a, 0x60],
+    [0x56, 0x99, 0x51, 0x2a, 0x6d, 0xd8, 0x20, 0xd3],
+    [0x66, 0x8b, 0x90, 0x7d, 0x1a, 0xdd, 0x4f, 0xcc],
+    [0x0c, 0xd8, 0xdb, 0x63, 0x90, 0x68, 0xf2, 0x9c],
+    [0x3e, 0xe6, 0x73, 0xb4, 0x9c, 0x38, 0xfc, 0x8f],
+    [0x1c, 0x7d, 0x29, 0x8d, 0xe5, 0x9d, 0x1f, 0xf2],
+    [0x40, 0xe0, 0xcc, 0xa6, 0x46, 0x2f, 0xdc, 0xc0],
+    [0x44, 0xf8, 0x45, 0x2b, 0xfe, 0xab, 0x92, 0xb9],
+    [0x2e, 0x87, 0x20, 0xa3, 0x9b, 0x7b, 0xfe, 0x7f],
+    [0x23, 0xc1, 0xe6, 0xda, 0x7f, 0x0e, 0x5a, 0x52],
+    [0x8c, 0x9c, 0x34, 0x67, 0xb2, 0xae, 0x64, 0xf4],
+    [0x79, 0x09, 0x5b, 0x70, 0x28, 0x59, 0xcd, 0x45],
+    [0xa5, 0x13, 0x99, 0xca, 0xe3, 0x35, 0x3e, 0x3a],
+    [0x35, 0x3b, 0xde, 0x4a, 0x4e, 0xc7, 0x1d, 0xa9],
+    [0x0d, 0xd0, 0x6c, 0xef, 0x02, 0xed, 0x0b, 0xfb],
+    [0xf4, 0xe1, 0xb1, 0x4a, 0xb4, 0x3c, 0xd9, 0x88],
+    [0x63, 0xe6, 0xc5, 0x43, 0xd6, 0x11, 0x0f, 0x54],
+    [0xbc, 0xd1, 0x21, 0x8c, 0x1f, 0xdd, 0x70, 0x23],
+    [0x0d, 0xb6, 0xa7, 0x16, 0x6c, 0x7b, 0x15, 0x81],
+    [0xbf, 0xf9, 0x8f, 0x7a, 0xe5, 0xb9, 0x54, 0x4d],
+    [0x3e, 0x75, 0x2a, 0x1f, 0x78, 0x12, 0x9f, 0x75],
+    [0x91, 0x6b, 0x18, 0xbf, 0xbe, 0xa3, 0xa1, 0xce],
+    [0x06, 0x62, 0xa2, 0xad, 0xd3, 0x08, 0xf5, 0x2c],
+    [0x57, 0x30, 0xc3, 0xa3, 0x2d, 0x1c, 0x10, 0xb6],
+    [0xa1, 0x36, 0x3a, 0xae, 0x96, 0x74, 0xf4, 0xb3],
+    [0x92, 0x83, 0x10, 0x7b, 0x54, 0x57, 0x6b, 0x62],
+    [0x31, 0x15, 0xe4, 0x99, 0x32, 0x36, 0xd2, 0xc1],
+    [0x44, 0xd9, 0x1a, 0x3f, 0x92, 0xc1, 0x7c, 0x66],
+    [0x25, 0x88, 0x13, 0xc8, 0xfe, 0x4f, 0x70, 0x65],
+    [0xa6, 0x49, 0x89, 0xc2, 0xd1, 0x80, 0xf2, 0x24],
+    [0x6b, 0x87, 0xf8, 0xfa, 0xed, 0x1c, 0xca, 0xc2],
+    [0x96, 0x21, 0x04, 0x9f, 0xfc, 0x4b, 0x16, 0xc2],
+    [0x23, 0xd6, 0xb1, 0x68, 0x93, 0x9c, 0x6e, 0xa1],
+    [0xfd, 0x14, 0x51, 0x8b, 0x9c, 0x16, 0xfb, 0x49],
+    [0x46, 0x4c, 0x07, 0xdf, 0xf8, 0x43, 0x31, 0x9f],
+    [0xb3, 0x86, 0xcc, 0x12, 0x24, 0xaf, 0xfd, 0xc6],
+    [0x8f, 0x09, 0x52, 0x0a, 0xd1, 0x49, 0xaf, 0x7e],
+    [0x9a, 0x2f, 0x29, 0x9d, 0x55, 0x13, 0xf3, 0x1c],
+    [0x12, 0x1f, 0xf4, 0xa2, 0xdd, 0x30, 0x4a, 0xc4],
+    [0xd0, 0x1e, 0xa7, 0x43, 0x89, 0xe9, 0xfa, 0x36],
+    [0xe6, 0xbc, 0xf0, 0x73, 0x4c, 0xb3, 0x8f, 0x31],
+    [0x80, 0xe9, 0xa7, 0x70, 0x36, 0xbf, 0x7a, 0xa2],
+    [0x75, 0x6d, 0x3c, 0x24, 0xdb, 0xc0, 0xbc, 0xb4],
+    [0x13, 0x15, 0xb7, 0xfd, 0x52, 0xd8, 0xf8, 0x23],
+    [0x08, 0x8a, 0x7d, 0xa6, 0x4d, 0x5f, 0x03, 0x8f],
+    [0x48, 0xf1, 0xe8, 0xb7, 0xe5, 0xd0, 0x9c, 0xd8],
+    [0xee, 0x44, 0xa6, 0xf7, 0xbc, 0xe6, 0xf4, 0xf6],
+    [0xf2, 0x37, 0x18, 0x0f, 0xd8, 0x9a, 0xc5, 0xae],
+    [0xe0, 0x94, 0x66, 0x4b, 0x15, 0xf6, 0xb2, 0xc3],
+    [0xa8, 0xb3, 0xbb, 0xb7, 0x62, 0x90, 0x19, 0x9d],
+];
+
+/// Same as `VECTORS`, for SipHash-2-4 with 128-bit output.
+const VECTORS_128: [[u8; 16]; 64] = [
+    [0xa3, 0x81, 0x7f, 0x04, 0xba, 0x25, 0xa8, 0xe6, 0x6d, 0xf6, 0x72, 0x14, 0xc7, 0x55, 0x02, 0x93],
+    [0xda, 0x87, 0xc1, 0xd8, 0x6b, 0x99, 0xaf, 0x44, 0x34, 0x76, 0x59, 0x11, 0x9b, 0x22, 0xfc, 0x45],
+    [0x81, 0x77, 0x22, 0x8d, 0xa4, 0xa4, 0x5d, 0xc7, 0xfc, 0xa3, 0x8b, 0xde, 0xf6, 0x0a, 0xff, 0xe4],
+    [0x9c, 0x70, 0xb6, 0x0c, 0x52, 0x67, 0xa9, 0x4e, 0x5f, 0x33, 0xb6, 0xb0, 0x29, 0x85, 0xed, 0x51],
+    [0xf8, 0x81, 0x64, 0xc1, 0x2d, 0x9c, 0x8f, 0xaf, 0x7d, 0x0f, 0x6e, 0x7c, 0x7b, 0xcd, 0x55, 0x79],
+    [0x13, 0x68, 0x87, 0x59, 0x80, 0x77, 0x6f, 0x88, 0x54, 0x52, 0x7a, 0x07, 0x69, 0x0e, 0x96, 0x27],
+    [0x14, 0xee, 0xca, 0x33, 0x8b, 0x20, 0x86, 0x13, 0x48, 0x5e, 0xa0, 0x30, 0x8f, 0xd7, 0xa1, 0x5e],
+    [0xa1, 0xf1, 0xeb, 0xbe, 0xd8, 0xdb, 0xc1, 0x53, 0xc0, 0xb8, 0x4a, 0xa6, 0x1f, 0xf0, 0x82, 0x39],
+    [0x3b, 0x62, 0xa9, 0xba, 0x62, 0x58, 0xf5, 0x61, 0x0f, 0x83, 0xe2, 0x64, 0xf3, 0x14, 0x97, 0xb4],
+    [0x26, 0x44, 0x99, 0x06, 0x0a, 0xd9, 0xba, 0xab, 0xc4, 0x7f, 0x8b, 0x02, 0xbb, 0x6d, 0x71, 0xed],
+    [0x00, 0x11, 0x0d, 0xc3, 0x78, 0x14, 0x69, 0x56, 0xc9, 0x54, 0x47, 0xd3, 0xf3, 0xd0, 0xfb, 0xba],
+    [0x01, 0x51, 0xc5, 0x68, 0x38, 0x6b, 0x66, 0x77, 0xa2, 0xb4, 0xdc, 0x6f, 0x81, 0xe5, 0xdc, 0x18],
+    [0xd6, 0x26, 0xb2, 0x66, 0x90, 0x5e, 0xf3, 0x58, 0x82, 0x63, 0x4d, 0xf6, 0x85, 0x32, 0xc1, 0x25],
+    [0x98, 0x69, 0xe2, 0x47, 0xe9, 0xc0, 0x8b, 0x10, 0xd0, 0x29, 0x93, 0x4f, 0xc4, 0xb9, 0x52, 0xf7],
+    [0x31, 0xfc, 0xef, 0xac, 0x66, 0xd7, 0xde, 0x9c, 0x7e, 0xc7, 0x48, 0x5f, 0xe4, 0x49, 0x49, 0x02],
+    [0x54, 0x93, 0xe9, 0x99, 0x33, 0xb0, 0xa8, 0x11, 0x7e, 0x08, 0xec, 0x0f, 0x97, 0xcf, 0xc3, 0xd9],
+    [0x6e, 0xe2, 0xa4, 0xca, 0x67, 0xb0, 0x54, 0xbb, 0xfd, 0x33, 0x15, 0xbf, 0x85, 0x23, 0x05, 0x77],
+    [0x47, 0x3d, 0x06, 0xe8, 0x73, 0x8d, 0xb8, 0x98, 0x54, 0xc0, 0x66, 0xc4, 0x7a, 0xe4, 0x77, 0x40],
+    [0xa4, 0x26, 0xe5, 0xe4, 0x23, 0xbf, 0x48, 0x85, 0x29, 0x4d, 0xa4, 0x81, 0xfe, 0xae, 0xf7, 0x23],
+    [0x78, 0x01, 0x77, 0x31, 0xcf, 0x65, 0xfa, 0xb0, 0x74, 0xd5, 0x20, 0x89, 0x52, 0x51, 0x2e, 0xb1],
+    [0x9e, 0x25, 0xfc, 0x83, 0x3f, 0x22, 0x90, 0x73, 0x3e, 0x93, 0x44, 0xa5, 0xe8, 0x38, 0x39, 0xeb],
+    [0x56, 0x8e, 0x49, 0x5a, 0xbe, 0x52, 0x5a, 0x21, 0x8a, 0x22, 0x14, 0xcd, 0x3e, 0x07, 0x1d, 0x12],
+    [0x4a, 0x29, 0xb5, 0x45, 0x52, 0xd1, 0x6b, 0x9a, 0x46, 0x9c, 0x10, 0x52, 0x8e, 0xff, 0x0a, 0xae],
+    [0xc9, 0xd1, 0x84, 0xdd, 0xd5, 0xa9, 0xf5, 0xe0, 0xcf, 0x8c, 0xe2, 0x9a, 0x9a, 0xbf, 0x69, 0x1c],
+    [0x2d, 0xb4, 0x79, 0xae, 0x78, 0xbd, 0x50, 0xd8, 0x88, 0x2a, 0x8a, 0x17, 0x8a, 0x61, 0x32, 0xad],
+    [0x8e, 0xce, 0x5f, 0x04, 0x2d, 0x5e, 0x44, 0x7b, 0x50, 0x51, 0xb9, 0xea, 0xcb, 0x8d, 0x8f, 0x6f],
+    [0x9c, 0x0b, 0x53, 0xb4, 0xb3, 0xc3, 0x07, 0xe8, 0x7e, 0xae, 0xe0, 0x86, 0x78, 0x14, 0x1f, 0x66],
+    [0xab, 0xf2, 0x48, 0xaf, 0x69, 0xa6, 0xea, 0xe4, 0xbf, 0xd3, 0xeb, 0x2f, 0x12, 0x9e, 0xeb, 0x94],
+    [0x06, 0x64, 0xda, 0x16, 0x68, 0x57, 0x4b, 0x88, 0xb9, 0x35, 0xf3, 0x02, 0x73, 0x58, 0xae, 0xf4],
+    [0xaa, 0x4b, 0x9d, 0xc4, 0xbf, 0x33, 0x7d, 0xe9, 0x0c, 0xd4, 0xfd, 0x3c, 0x46, 0x7c, 0x6a, 0xb7],
+    [0xea, 0x5c, 0x7f, 0x47, 0x1f, 0xaf, 0x6b, 0xde, 0x2b, 0x1a, 0xd7, 0xd4, 0x68, 0x6d, 0x22, 0x87],
+    [0x29, 0x39, 0xb0, 0x18, 0x32, 0x23, 0xfa, 0xfc, 0x17, 0x23, 0xde, 0x4f, 0x52, 0xc4, 0x3d, 0x35],
+    [0x7c, 0x39, 0x56, 0xca, 0x5e, 0xea, 0xfc, 0x3e, 0x36, 0x3e, 0x9d, 0x55, 0x65, 0x46, 0xeb, 0x68],
+    [0x77, 0xc6, 0x07, 0x71, 0x46, 0xf0, 0x1c, 0x32, 0xb6, 0xb6, 0x9d, 0x5f, 0x4e, 0xa9, 0xff, 0xcf],
+    [0x37, 0xa6, 0x98, 0x6c, 0xb8, 0x84, 0x7e, 0xdf, 0x09, 0x25, 0xf0, 0xf1, 0x30, 0x9b, 0x54, 0xde],
+    [0xa7, 0x05, 0xf0, 0xe6, 0x9d, 0xa9, 0xa8, 0xf9, 0x07, 0x24, 0x1a, 0x2e, 0x92, 0x3c, 0x8c, 0xc8],
+    [0x3d, 0xc4, 0x7d, 0x1f, 0x29, 0xc4, 0x48, 0x46, 0x1e, 0x9e, 0x76, 0xed, 0x90, 0x4f, 0x67, 0x11],
+    [0x0d, 0x62, 0xbf, 0x01, 0xe6, 0xfc, 0x0e, 0x1a, 0x0d, 0x3c, 0x47, 0x51, 0xc5, 0xd3, 0x69, 0x2b],
+    [0x8c, 0x03, 0x46, 0x8b, 0xca, 0x7c, 0x66, 0x9e, 0xe4, 0xfd, 0x5e, 0x08, 0x4b, 0xbe, 0xe7, 0xb5],
+    [0x52, 0x8a, 0x5b, 0xb9, 0x3b, 0xaf, 0x2c, 0x9c, 0x44, 0x73, 0xcc, 0xe5, 0xd0, 0xd2, 0x2b, 0xd9],
+    [0xdf, 0x6a, 0x30, 0x1e, 0x95, 0xc9, 0x5d, 0xad, 0x97, 0xae, 0x0c, 0xc8, 0xc6, 0x91, 0x3b, 0xd8],
+    [0x80, 0x11, 0x89, 0x90, 0x2c, 0x85, 0x7f, 0x39, 0xe7, 0x35, 0x91, 0x28, 0x5e, 0x70, 0xb6, 0xdb],
+    [0xe6, 0x17, 0x34, 0x6a, 0xc9, 0xc2, 0x31, 0xbb, 0x36, 0x50, 0xae, 0x34, 0xcc, 0xca, 0x0c, 0x5b],
+    [0x27, 0xd9, 0x34, 0x37, 0xef, 0xb7, 0x21, 0xaa, 0x40, 0x18, 0x21, 0xdc, 0xec, 0x5a, 0xdf, 0x89],
+    [0x89, 0x23, 0x7d, 0x9d, 0xed, 0x9c, 0x5e, 0x78, 0xd8, 0xb1, 0xc9, 0xb1, 0x66, 0xcc, 0x73, 0x42],
+    [0x4a, 0x6d, 0x80, 0x91, 0xbf, 0x5e, 0x7d, 0x65, 0x11, 0x89, 0xfa, 0x94, 0xa2, 0x50, 0xb1, 0x4c],
+    [0x0e, 0x33, 0xf9, 0x60, 0x55, 0xe7, 0xae, 0x89, 0x3f, 0xfc, 0x0e, 0x3d, 0xcf, 0x49, 0x29, 0x02],
+    [0xe6, 0x1c, 0x43, 0x2b, 0x72, 0x0b, 0x19, 0xd1, 0x8e, 0xc8, 0xd8, 0x4b, 0xdc, 0x63, 0x15, 0x1b],
+    [0xf7, 0xe5, 0xae, 0xf5, 0x49, 0xf7, 0x82, 0xcf, 0x37, 0x90, 0x55, 0xa6, 0x08, 0x26, 0x9b, 0x16],
+    [0x43, 0x8d, 0x03, 0x0f, 0xd0, 0xb7, 0xa5, 0x4f, 0xa8, 0x37, 0xf2, 0xad, 0x20, 0x1a, 0x64, 0x03],
+    [0xa5, 0x90, 0xd3, 0xee, 0x4f, 0xbf, 0x04, 0xe3, 0x24, 0x7e, 0x0d, 0x27, 0xf2, 0x86, 0x42, 0x3f],
+    [0x5f, 0xe2, 0xc1, 0xa1, 0x72, 0xfe, 0x93, 0xc4, 0xb1, 0x5c, 0xd3, 0x7c, 0xae, 0xf9, 0xf5, 0x38],
+    [0x2c, 0x97, 0x32, 0x5c, 0xbd, 0x06, 0xb3, 0x6e, 0xb2, 0x13, 0x3d, 0xd0, 0x8b, 0x3a, 0x01, 0x7c],
+    [0x92, 0xc8, 0x14, 0x22, 0x7a, 0x6b, 0xca, 0x94, 0x9f, 0xf0, 0x65, 0x9f, 0x00, 0x2a, 0xd3, 0x9e],
+    [0xdc, 0xe8, 0x50, 0x11, 0x0b, 0xd8, 0x32, 0x8c, 0xfb, 0xd5, 0x08, 0x41, 0xd6, 0x91, 0x1d, 0x87],
+    [0x67, 0xf1, 0x49, 0x84, 0xc7, 0xda, 0x79, 0x12, 0x48, 0xe3, 0x2b, 0xb5, 0x92, 0x25, 0x83, 0xda],
+    [0x19, 0x38, 0xf2, 0xcf, 0x72, 0xd5, 0x4e, 0xe9, 0x7e, 0x94, 0x16, 0x6f, 0xa9, 0x1d, 0x2a, 0x36],
+    [0x74, 0x48, 0x1e, 0x96, 0x46, 0xed, 0x49, 0xfe, 0x0f, 0x62, 0x24, 0x30, 0x16, 0x04, 0x69, 0x8e],
+    [0x57, 0xfc, 0xa5, 0xde, 0x98, 0xa9, 0xd6, 0xd8, 0x00, 0x64, 0x38, 0xd0, 0x58, 0x3d, 0x8a, 0x1d],
+    [0x9f, 0xec, 0xde, 0x1c, 0xef, 0xdc, 0x1c, 0xbe, 0xd4, 0x76, 0x36, 0x74, 0xd9, 0x57, 0x53, 0x59],
+    [0xe3, 0x04, 0x0c, 0x00, 0xeb, 0x28, 0xf1, 0x53, 0x66, 0xca, 0x73, 0xcb, 0xd8, 0x72, 0xe7, 0x40],
+    [0x76, 0x97, 0x00, 0x9a, 0x6a, 0x83, 0x1d, 0xfe, 0xcc, 0xa9, 0x1c, 0x59, 0x93, 0x67, 0x0f, 0x7a],
+    [0x58, 0x53, 0x54, 0x23, 0x21, 0xf5, 0x67, 0xa0, 0x05, 0xd5, 0x47, 0xa4, 0xf0, 0x47, 0x59, 0xbd],
+    [0x51, 0x50, 0xd1, 0x77, 0x2f, 0x50, 0x83, 0x4a, 0x50, 0x3e, 0x06, 0x9a, 0x97, 0x3f, 0xbd, 0x7c],
+];
+
+const K0: u64 = 0x0706050403020100;
+const K1: u64 = 0x0f0e0d0c0b0a0908;
+
+/// Feed `data` to `ctx` in chunks of the given lengths (cycled, clamped
+/// to what is left), so that empty updates and updates that exactly fill
+/// the tail are exercised too. Splits that are all empty, which would
+/// never make progress, feed `data` in one update.
+fn update_split<C: HashContext>(ctx: &mut C, data: &[u8], splits: &[usize]) {
+    let splits = if splits.iter().all(|&n| n == 0) { &[][..] } else { splits };
+    let mut rest = data;
+    let mut splits = splits.iter().cycle();
+    while !rest.is_empty() {
+        let n = match splits.next() {
+            Some(&n) => std::cmp::min(n, rest.len()),
+            None => rest.len(),
+        };
+        ctx.update(&rest[..n]);
+        rest = &rest[n..];
+    }
+}
+
+#[allow(deprecated)]
+fn std_siphash(k0: u64, k1: u64, data: &[u8]) -> u64 {
+    use std::hash::Hasher;
+
+    let mut hasher = std::hash::SipHasher::new_with_keys(k0, k1);
+    hasher.write(data);
+    hasher.finish()
+}
+
+#[test]
+fn reference_vectors() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let msg: Vec<u8> = (0..64).collect();
+    for (len, expected) in VECTORS.iter().enumerate() {
+        let expected = u64::from_le_bytes(*expected);
+        assert_eq!(f.digest(&msg[..len]), expected, "one-shot, length {}", len);
+
+        let mut ctx = f.init();
+        for b in &msg[..len] {
+            ctx.update(std::slice::from_ref(b));
+        }
+        assert_eq!(ctx.finish(), expected, "byte by byte, length {}", len);
+    }
+}
+
+#[test]
+fn reference_vectors_sip13() {
+    let f = Sip13HashFunction::new_with_keys(K0, K1);
+    let msg: Vec<u8> = (0..64).collect();
+    for (len, expected) in VECTORS_13.iter().enumerate() {
+        let expected = u64::from_le_bytes(*expected);
+        assert_eq!(f.digest(&msg[..len]), expected, "one-shot, length {}", len);
+
+        let mut ctx = f.init();
+        update_split(&mut ctx, &msg[..len], &[1, 2, 7]);
+        assert_eq!(ctx.finish(), expected, "split, length {}", len);
+    }
+    // the round counts take effect
+    assert_ne!(VECTORS_13[0], VECTORS[0]);
+}
+
+#[test]
+fn reference_vectors_128() {
+    let f = SipHash128Function::new_with_keys(K0, K1);
+    let msg: Vec<u8> = (0..64).collect();
+    for (len, expected) in VECTORS_128.iter().enumerate() {
+        let expected = u128::from_le_bytes(*expected);
+        assert_eq!(f.digest(&msg[..len]), expected, "one-shot, length {}", len);
+
+        let mut ctx = f.init();
+        update_split(&mut ctx, &msg[..len], &[1, 2, 7]);
+        assert_eq!(ctx.finish(), expected, "split, length {}", len);
+    }
+}
+
+#[test]
+fn tail_edge_cases() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let msg: Vec<u8> = (0..64).collect();
+    // empty updates everywhere, a 7-byte tail filled by 1 byte, updates
+    // smaller than the remaining tail
+    for splits in [&[0, 3, 0, 4, 0, 1][..], &[7, 1], &[5, 1, 1, 1, 8], &[1, 2]] {
+        for len in 0..msg.len() {
+            let mut ctx = f.init();
+            update_split(&mut ctx, &msg[..len], splits);
+            assert_eq!(ctx.finish(), u64::from_le_bytes(VECTORS[len]), "length {}, splits {:?}", len, splits);
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn split_updates_match_oneshot_and_std(
+        k0: u64,
+        k1: u64,
+        data in proptest::collection::vec(any::<u8>(), 0..512),
+        splits in proptest::collection::vec(0usize..40, 0..32),
+    ) {
+        let f = SipHashFunction::new_with_keys(k0, k1);
+        let mut ctx = f.init();
+        update_split(&mut ctx, &data, &splits);
+        let streamed = ctx.finish();
+
+        prop_assert_eq!(streamed, f.digest(&data));
+        prop_assert_eq!(streamed, std_siphash(k0, k1, &data));
+    }
+
+    #[test]
+    fn split_updates_match_oneshot_sip13(
+        k0: u64,
+        k1: u64,
+        data in proptest::collection::vec(any::<u8>(), 0..512),
+        splits in proptest::collection::vec(0usize..40, 0..32),
+    ) {
+        let f = Sip13HashFunction::new_with_keys(k0, k1);
+        let mut ctx = f.init();
+        update_split(&mut ctx, &data, &splits);
+        let streamed = ctx.finish();
+
+        prop_assert_eq!(streamed, f.digest(&data));
+        let mut reference = siphasher::sip::SipHasher13::new_with_keys(k0, k1);
+        std::hash::Hasher::write(&mut reference, &data);
+        prop_assert_eq!(streamed, std::hash::Hasher::finish(&reference));
+    }
+
+    #[test]
+    fn split_updates_match_oneshot_sip128(
+        k0: u64,
+        k1: u64,
+        data in proptest::collection::vec(any::<u8>(), 0..512),
+        splits in proptest::collection::vec(0usize..40, 0..32),
+    ) {
+        use siphasher::sip128::Hasher128;
+
+        let f = SipHash128Function::new_with_keys(k0, k1);
+        let mut ctx = f.init();
+        update_split(&mut ctx, &data, &splits);
+        let streamed = ctx.finish();
+
+        prop_assert_eq!(streamed, f.digest(&data));
+        let mut reference = siphasher::sip128::SipHasher::new_with_keys(k0, k1);
+        std::hash::Hasher::write(&mut reference, &data);
+        prop_assert_eq!(streamed, reference.finish128().as_u128());
+    }
+
+    #[test]
+    fn typed_writes_match_bytes(
+        k0: u64,
+        k1: u64,
+        prefix in proptest::collection::vec(any::<u8>(), 0..16),
+        values in proptest::collection::vec(any::<u64>(), 0..16),
+    ) {
+        let f = SipHashFunction::new_with_keys(k0, k1);
+        let mut typed = f.init();
+        let mut bytes = f.init();
+        typed.update(&prefix);
+        bytes.update(&prefix);
+        for (i, &v) in values.iter().enumerate() {
+            match i % 4 {
+                0 => { typed.write_u8(v as u8); bytes.update(&[v as u8]); }
+                1 => { typed.write_u16(v as u16); bytes.update(&(v as u16).to_le_bytes()); }
+                2 => { typed.write_u32(v as u32); bytes.update(&(v as u32).to_le_bytes()); }
+                _ => { typed.write_u64(v); bytes.update(&v.to_le_bytes()); }
+            }
+        }
+        prop_assert_eq!(typed.finish(), bytes.finish());
+    }
+
+    #[test]
+    fn finish_reset_matches_fresh_context(
+        k0: u64,
+        k1: u64,
+        inputs in proptest::collection::vec(proptest::collection::vec(any::<u8>(), 0..64), 0..8),
+    ) {
+        let f = SipHashFunction::new_with_keys(k0, k1);
+        let mut ctx = f.init();
+        for input in &inputs {
+            ctx.update(input);
+            prop_assert_eq!(ctx.finish_reset(), f.digest(input));
+        }
+    }
+}
+
+#[test]
+fn nonces_separate_tags() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let msg = b"transfer 100 to bob";
+    let tag = f.digest_nonced(1, msg);
+    assert_ne!(tag, f.digest_nonced(2, msg));
+    assert_ne!(tag, f.digest(msg));
+
+    // the verifier must use the nonce of the message
+    assert_eq!(f.digest_nonced(1, msg), tag);
+    assert_ne!(f.digest_nonced(2, msg), tag);
+}
+
+#[test]
+fn wrapped_digest_matches_linear() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let data: Vec<u8> = (0..40).collect();
+    for split in 0..=data.len() {
+        let (head, tail) = data.split_at(split);
+        assert_eq!(f.digest_wrapped(head, tail), f.digest(&data));
+    }
+    assert_eq!(f.digest_wrapped(b"", b""), f.digest(b""));
+}
+
+#[test]
+fn sampled_digests_include_the_length() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let data: Vec<u8> = (0..1000u32).map(|i| (i % 251) as u8).collect();
+    assert_eq!(f.digest_sampled(&data, 7), f.digest_sampled(&data, 7));
+    assert_ne!(f.digest_sampled(&data, 7), f.digest_sampled(&data, 8));
+
+    // both sample [a, c], only the length differs
+    assert_ne!(f.digest_sampled(b"abc", 2), f.digest_sampled(b"abcd", 2));
+    // skipped bytes are not hashed
+    assert_eq!(f.digest_sampled(b"abc", 2), f.digest_sampled(b"axc", 2));
+}
+
+#[test]
+fn blocks_are_digested_separately() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let mut data = Vec::new();
+    data.extend_from_slice(b"0123456789abcdef");
+    data.extend_from_slice(b"ghijklmnopqrstuv");
+    data.extend_from_slice(b"0123456789abcdef");
+    data.extend_from_slice(b"tail");
+
+    let blocks = f.digest_blocks(&data, 16);
+    assert_eq!(blocks.len(), 4);
+    assert_eq!(blocks[0], blocks[2]);
+    assert_ne!(blocks[0], blocks[1]);
+    assert_eq!(blocks[0], f.digest(b"0123456789abcdef"));
+    assert_eq!(blocks[3], f.digest(b"tail"));
+    assert!(f.digest_blocks(b"", 16).is_empty());
+}
+
+struct Order {
+    id: u64,
+    customer: String,
+}
+
+impl Hash for Order {
+    fn hash<H: HashContext>(&self, ctx: &mut H) {
+        self.id.hash(ctx);
+        self.customer.hash(ctx);
+    }
+}
+
+#[test]
+fn hash_keys_are_digests() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let order = Order { id: 42, customer: "alice".to_string() };
+    assert_eq!(order.hash_key(&f), order.digest(&f));
+    assert_eq!(order.hash_key(&f), (42u64, "alice").digest(&f));
+    assert_eq!("alice".hash_key(&f), "alice".digest(&f));
+}
+
+#[test]
+fn variable_width_matches_dedicated_functions() {
+    let v = SipVariable::new_with_keys(K0, K1);
+    let narrow = SipHashFunction::new_with_keys(K0, K1);
+    let wide = SipHash128Function::new_with_keys(K0, K1);
+    let data: Vec<u8> = (0..64).collect();
+    for n in 0..data.len() {
+        let msg = &data[..n];
+        assert_eq!(v.digest(msg, false), SipOutput::U64(narrow.digest(msg)));
+        assert_eq!(v.digest(msg, true), SipOutput::U128(wide.digest(msg).to_le_bytes()));
+        assert_eq!(v.digest(msg, false), SipOutput::U64(u64::from_le_bytes(VECTORS[n])));
+    }
+
+    // first 128-bit vector of the reference implementation
+    assert_eq!(v.digest(b"", true),
+               SipOutput::U128([0xa3, 0x81, 0x7f, 0x04, 0xba, 0x25, 0xa8, 0xe6,
+                                0x6d, 0xf6, 0x72, 0x14, 0xc7, 0x55, 0x02, 0x93]));
+
+    let key = Key::new(K0, K1);
+    assert_eq!(SipVariable::from_key(&key).digest(b"abc", true), v.digest(b"abc", true));
+}
+
+#[test]
+fn xor_digests_fingerprint_changes() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let a: Vec<u8> = (0..100).collect();
+    assert_eq!(f.digest_xor(&a, &a), Some(f.digest(&[0; 100])));
+
+    let mut b = a.clone();
+    b[70] ^= 0x10;
+    let mut changed = [0u8; 100];
+    changed[70] = 0x10;
+    assert_eq!(f.digest_xor(&a, &b), Some(f.digest(&changed)));
+    assert_ne!(f.digest_xor(&a, &b), f.digest_xor(&a, &a));
+
+    assert_eq!(f.digest_xor(&a, &a[..99]), None);
+}
+
+#[test]
+fn time_buckets_rotate_at_window_boundaries() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let id = b"user-1234";
+    let window = 3600;
+    let first = f.digest_time_bucketed(id, 7200, window);
+    assert_eq!(f.digest_time_bucketed(id, 7200 + 1, window), first);
+    assert_eq!(f.digest_time_bucketed(id, 7200 + 3599, window), first);
+    assert_ne!(f.digest_time_bucketed(id, 7200 + 3600, window), first);
+    assert_ne!(f.digest_time_bucketed(id, 7199, window), first);
+    assert_ne!(f.digest_time_bucketed(b"user-1235", 7200, window), first);
+}
+
+#[test]
+fn reversible_digests_ignore_direction() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    assert_eq!(f.digest_canonical_reversible(&[1, 2, 3]), f.digest_canonical_reversible(&[3, 2, 1]));
+    assert_ne!(f.digest_canonical_reversible(&[1, 2, 3]), f.digest_canonical_reversible(&[1, 3, 2]));
+
+    let data: Vec<u8> = (0..200).collect();
+    let reversed: Vec<u8> = data.iter().rev().copied().collect();
+    let canonical = f.digest_canonical_reversible(&data);
+    assert_eq!(canonical, f.digest_canonical_reversible(&reversed));
+    assert_eq!(canonical, f.digest(&data).min(f.digest(&reversed)));
+    assert_eq!(f.digest_canonical_reversible(b"abcba"), f.digest(b"abcba"));
+}
+
+#[test]
+fn deterministic_uuids_are_valid() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let uuid = f.deterministic_uuid(b"fixture-1");
+    assert_eq!(uuid, f.deterministic_uuid(b"fixture-1"));
+    assert_ne!(uuid, f.deterministic_uuid(b"fixture-2"));
+    for i in 0..100u32 {
+        let uuid = f.deterministic_uuid(&i.to_le_bytes());
+        assert_eq!(uuid[6] >> 4, 4);
+        assert_eq!(uuid[8] >> 6, 0b10);
+    }
+
+    // the helpers use distinct domains: the first half of the UUID used
+    // to be the time-bucketed digest of window 0 (bytes 6 and 8 hold the
+    // version and variant bits)
+    let uuid = f.deterministic_uuid(b"x");
+    assert_ne!(uuid[..6], f.digest_time_bucketed(b"x", 5, 10).to_le_bytes()[..6]);
+    assert_ne!(uuid[..6], f.digest_nonced(0, b"x").to_le_bytes()[..6]);
+}
+
+// Partial words are read with masked or shifted wide loads: check every
+// tail length at every position against std's byte-by-byte SipHash, for
+// buffers long enough for a whole-word load and for shorter ones.
+#[test]
+fn masked_tail_loads_match_bytewise_reference() {
+    let data: Vec<u8> = (0..64u32).map(|i| (i * 37 + 11) as u8).collect();
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    for start in 0..8 {
+        for len in 0..40 {
+            let msg = &data[start..start + len];
+            assert_eq!(f.digest(msg), std_siphash(K0, K1, msg), "start {}, len {}", start, len);
+            // a first update of `head` bytes leaves a partial word that
+            // the next update completes
+            for head in 0..=std::cmp::min(len, 8) {
+                let mut ctx = f.init();
+                ctx.update(&msg[..head]);
+                ctx.update(&msg[head..]);
+                assert_eq!(ctx.finish(), std_siphash(K0, K1, msg), "start {}, len {}, head {}", start, len, head);
+            }
+        }
+    }
+}
+
+#[test]
+fn expanded_subkeys_extend_the_prefix() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let keys = f.expand(b"master secret", 16);
+    assert_eq!(keys.len(), 16);
+    for (i, a) in keys.iter().enumerate() {
+        assert!(keys[i + 1..].iter().all(|b| a != b));
+    }
+    assert_eq!(f.expand(b"master secret", 16), keys);
+    assert_eq!(f.expand(b"master secret", 20)[..16], keys[..]);
+    assert_eq!(f.expand(b"master secret", 4)[..], keys[..4]);
+    assert!(f.expand(b"master secret", 0).is_empty());
+    assert_ne!(f.expand(b"other secret", 1), keys[..1]);
+
+    let mut input = b"master secret".to_vec();
+    input.extend_from_slice(&3u64.to_le_bytes());
+    assert_eq!(keys[3], f.digest(&input));
+}
+
+#[test]
+fn big_endian_words_match_their_swapped_forms() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let words: Vec<u64> = (0..20u64).map(|i| i.wrapping_mul(0x9e3779b97f4a7c15)).collect();
+    let big: Vec<u8> = words.iter().flat_map(|w| w.to_be_bytes()).collect();
+    let little: Vec<u8> = words.iter().flat_map(|w| w.to_le_bytes()).collect();
+    assert_eq!(f.digest_with_endianness(&big, true), f.digest_with_endianness(&little, false));
+    assert_eq!(f.digest_with_endianness(&little, false), f.digest(&little));
+    assert_ne!(f.digest_with_endianness(&big, true), f.digest(&big));
+
+    // the trailing partial word is not swapped
+    let mut big_tail = big;
+    big_tail.extend_from_slice(b"abc");
+    let mut little_tail = little;
+    little_tail.extend_from_slice(b"abc");
+    assert_eq!(f.digest_with_endianness(&big_tail, true), f.digest(&little_tail));
+}
+
+#[test]
+fn prefetching_digests_match_digest() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let data: Vec<u8> = (0..100_000u32).map(|i| (i * 31 % 251) as u8).collect();
+    // shorter than a chunk, around the prefetch distance, and unaligned
+    for &len in &[0, 1, 7, 1023, 1024, 4095, 4096, 4097, 10_001, data.len()] {
+        assert_eq!(f.digest_prefetch(&data[..len]), f.digest(&data[..len]), "length {}", len);
+    }
+    assert_eq!(f.digest_prefetch(&data[3..]), f.digest(&data[3..]));
+}
+
+#[test]
+fn empty_short_and_aligned_updates() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let msg: Vec<u8> = (0..64).collect();
+
+    let mut ctx = f.init();
+    ctx.update(&[]);
+    ctx.update(&[]);
+    assert_eq!(ctx.finish(), u64::from_le_bytes(VECTORS[0]));
+
+    let mut ctx = f.init();
+    ctx.update(&msg[..3]);
+    ctx.update(&[]);
+    // does not fill the tail
+    ctx.update(&msg[3..5]);
+    ctx.update(&[]);
+    // fills the tail exactly
+    ctx.update(&msg[5..8]);
+    // aligned updates with an empty tail
+    ctx.update(&msg[8..24]);
+    ctx.update(&msg[24..32]);
+    ctx.update(&[]);
+    assert_eq!(ctx.finish(), u64::from_le_bytes(VECTORS[32]));
+}
+
+#[test]
+fn counted_digests_ignore_order_but_not_counts() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    assert_eq!(f.digest_counted(&["a", "b", "a"]), f.digest_counted(&["b", "a", "a"]));
+    assert_ne!(f.digest_counted(&["a", "a", "b"]), f.digest_counted(&["a", "b", "b"]));
+    assert_ne!(f.digest_counted(&["a", "b"]), f.digest_counted(&["a", "a", "b", "b"]));
+    assert_ne!(f.digest_counted(&[1u32]), f.digest_counted::<u32>(&[]));
+
+    let mut ctx = f.init();
+    ("a", 2u64).hash(&mut ctx);
+    ("b", 1u64).hash(&mut ctx);
+    assert_eq!(f.digest_counted(&["b", "a", "a"]), ctx.finish());
+}
+
+#[test]
+fn sparse_digests_depend_on_the_logical_vector() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let vector = [(3, 1.5), (10, -2.0), (7, 0.25)];
+    let digest = f.digest_sparse(&vector);
+    assert_eq!(f.digest_sparse(&[(10, -2.0), (7, 0.25), (3, 1.5)]), digest);
+    assert_eq!(f.digest_sparse(&[(0, 0.0), (3, 1.5), (5, -0.0), (10, -2.0), (7, 0.25)]), digest);
+    assert_ne!(f.digest_sparse(&[(3, 1.5), (10, -2.0)]), digest);
+    assert_ne!(f.digest_sparse(&[(3, 1.5), (10, 2.0), (7, 0.25)]), digest);
+    assert_ne!(f.digest_sparse(&[(4, 1.5), (10, -2.0), (7, 0.25)]), digest);
+
+    assert_eq!(f.digest_sparse(&[]), f.digest_sparse(&[(1, 0.0)]));
+    assert_eq!(f.digest_sparse(&[(1, f64::NAN)]), f.digest_sparse(&[(1, -f64::NAN)]));
+}
+
+#[test]
+fn prefixed_digests_match_streamed_slices() {
+    fn streamed<F: HashFunction>(f: &F, bytes: &[u8]) -> <F::Context as HashContext>::Result {
+        let mut ctx = f.init();
+        bytes.hash(&mut ctx);
+        ctx.finish()
+    }
+
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let f13 = Sip13HashFunction::new_with_keys(K0, K1);
+    let f128 = SipHash128Function::new_with_keys(K0, K1);
+    let msg: Vec<u8> = (0..64).collect();
+    for len in 0..msg.len() {
+        let bytes = &msg[..len];
+        let mut prefixed = (len as u64).to_le_bytes().to_vec();
+        prefixed.extend_from_slice(bytes);
+
+        assert_eq!(f.digest_prefixed(bytes), f.digest(&prefixed), "length {}", len);
+        assert_eq!(bytes.digest(&f), streamed(&f, bytes), "length {}", len);
+        assert_eq!(bytes.to_vec().digest(&f), streamed(&f, bytes), "length {}", len);
+        assert_eq!(Box::new(bytes).digest(&f), streamed(&f, bytes), "length {}", len);
+        assert_eq!(bytes.digest(&f13), streamed(&f13, bytes), "length {}", len);
+        assert_eq!(bytes.digest(&f128), streamed(&f128, bytes), "length {}", len);
+    }
+    assert_eq!("abc".digest(&f), streamed(&f, b"abc"));
+    assert_eq!(String::from("abc").digest(&f), streamed(&f, b"abc"));
+}
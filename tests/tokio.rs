@@ -0,0 +1,35 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use hash::sip::SipHashFunction;
+use hash::traits::HashFunction;
+
+#[test]
+fn async_digest_yields_to_other_tasks() {
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let f = SipHashFunction::new_with_keys(1, 2);
+    let data: Vec<u8> = (0..1 << 20).map(|i| (i * 7) as u8).collect();
+
+    // on a single-threaded runtime the ticker only runs when the digest
+    // yields
+    let ticks = Arc::new(AtomicUsize::new(0));
+    let digest = runtime.block_on(async {
+        let ticker = {
+            let ticks = ticks.clone();
+            tokio::spawn(async move {
+                loop {
+                    ticks.fetch_add(1, Ordering::Relaxed);
+                    tokio::task::yield_now().await;
+                }
+            })
+        };
+        let digest = f.digest_async(&data).await;
+        ticker.abort();
+        digest
+    });
+
+    assert_eq!(digest, f.digest(&data));
+    assert!(ticks.load(Ordering::Relaxed) > 0);
+
+    assert_eq!(runtime.block_on(f.digest_async(b"")), f.digest(b""));
+}
@@ -0,0 +1,332 @@
+use hash::crc::{Crc32HashFunction, Crc32cHashFunction};
+use hash::fnv::FnvHashFunction;
+use hash::fx::FxHashFunction;
+use hash::murmur3::{Murmur3x128HashFunction, Murmur3x32HashFunction};
+use hash::rolling::BuzHash;
+use hash::sha2::Sha256HashFunction;
+use hash::sip::{SipHash128Function, SipHashFunction};
+use hash::traits::{digest_pod, Hash, HashContext, HashFunction, ResettableContext};
+use hash::tree::TreeHash;
+use hash::xxh::{Xxh3HashFunction, Xxh64HashFunction};
+
+const K0: u64 = 0x0706050403020100;
+const K1: u64 = 0x0f0e0d0c0b0a0908;
+
+/// Context recording the bytes it is fed, to check encodings.
+struct Bytes(Vec<u8>);
+
+impl HashContext for Bytes {
+    type Result = Vec<u8>;
+
+    fn update(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes)
+    }
+
+    fn finish(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+fn fed(f: impl FnOnce(&mut Bytes)) -> Vec<u8> {
+    let mut ctx = Bytes(Vec::new());
+    f(&mut ctx);
+    ctx.finish()
+}
+
+#[test]
+fn padded_digests_frame_the_length() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let padded = f.digest_padded(b"ab", 8);
+    assert_ne!(padded, f.digest(b"ab"));
+    assert_ne!(padded, f.digest(b"ab\0\0\0\0\0\0"));
+
+    // all of these pad to the same block, only the length tells them apart
+    assert_ne!(padded, f.digest_padded(b"ab\0", 8));
+    assert_ne!(padded, f.digest_padded(b"ab\0\0\0\0\0\0", 8));
+
+    // inputs that already fill the block are not padded
+    assert_eq!(f.digest_padded(b"abcdefgh", 8), f.digest_padded(b"abcdefgh", 4));
+    assert_eq!(f.digest_padded(b"", 8), f.digest(&0u64.to_le_bytes()));
+}
+
+#[test]
+fn delta_updates_feed_the_differences() {
+    assert_eq!(fed(|ctx| assert_eq!(ctx.update_delta(&[0, 1, 2, 3], 0), 3)), [0, 1, 1, 1]);
+    assert_eq!(fed(|ctx| { ctx.update_delta(&[5, 3, 0, 255], 10); }), [251, 254, 253, 255]);
+
+    let ramp: Vec<u8> = (0..200).collect();
+    let chained = fed(|ctx| {
+        let prev = ctx.update_delta(&ramp[..77], 0);
+        ctx.update_delta(&ramp[77..], prev);
+    });
+    assert_eq!(chained, fed(|ctx| { ctx.update_delta(&ramp, 0); }));
+    assert_eq!(chained[0], 0);
+    assert!(chained[1..].iter().all(|&d| d == 1));
+
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let mut ctx = f.init();
+    ctx.update_delta(&[0, 1, 2, 3], 0);
+    assert_eq!(ctx.finish(), f.digest(&[0, 1, 1, 1]));
+}
+
+#[test]
+fn varints_feed_leb128() {
+    assert_eq!(fed(|ctx| ctx.update_varint(0)), [0x00]);
+    assert_eq!(fed(|ctx| ctx.update_varint(1)), [0x01]);
+    assert_eq!(fed(|ctx| ctx.update_varint(127)), [0x7f]);
+    assert_eq!(fed(|ctx| ctx.update_varint(128)), [0x80, 0x01]);
+    assert_eq!(fed(|ctx| ctx.update_varint(300)), [0xac, 0x02]);
+    assert_eq!(fed(|ctx| ctx.update_varint(u64::MAX)),
+               [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]);
+}
+
+#[test]
+fn integers_feed_fixed_width_little_endian() {
+    assert_eq!(fed(|ctx| 0x0102u16.hash(ctx)), [0x02, 0x01]);
+    assert_eq!(fed(|ctx| 0x0102_0304u32.hash(ctx)), [0x04, 0x03, 0x02, 0x01]);
+    assert_eq!(fed(|ctx| (-2i32).hash(ctx)), [0xfe, 0xff, 0xff, 0xff]);
+    assert_eq!(fed(|ctx| 1u128.hash(ctx)), 1u128.to_le_bytes());
+    // a 32-bit `usize` is widened to the 64-bit encoding
+    let small = u32::MAX as usize;
+    assert_eq!(fed(|ctx| small.hash(ctx)), (u32::MAX as u64).to_le_bytes());
+    assert_eq!(fed(|ctx| (-1isize).hash(ctx)), [0xff; 8]);
+
+    // the bytes 00 .. 07, i.e. the SipHash-2-4 reference vector of length 8
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    assert_eq!(0x0706050403020100u64.digest(&f), 0x93f5f5799a932462);
+}
+
+#[test]
+fn bools_chars_and_floats_feed_canonical_bytes() {
+    assert_eq!(fed(|ctx| true.hash(ctx)), [1]);
+    assert_eq!(fed(|ctx| false.hash(ctx)), [0]);
+    assert_eq!(fed(|ctx| 'é'.hash(ctx)), 0xe9u32.to_le_bytes());
+    assert_eq!(fed(|ctx| 1.5f64.hash(ctx)), 1.5f64.to_bits().to_le_bytes());
+    assert_eq!(fed(|ctx| 1.5f32.hash(ctx)), 1.5f32.to_bits().to_le_bytes());
+    assert_eq!(fed(|ctx| (-1i64).hash(ctx)), [0xff; 8]);
+
+    // zeros of either sign agree, and so do all NaNs
+    assert_eq!(fed(|ctx| (-0.0f64).hash(ctx)), [0; 8]);
+    assert_eq!(fed(|ctx| (-0.0f32).hash(ctx)), [0; 4]);
+    let nan = fed(|ctx| f64::NAN.hash(ctx));
+    assert_eq!(fed(|ctx| (-f64::NAN).hash(ctx)), nan);
+    assert_eq!(fed(|ctx| f64::from_bits(0x7ff0_0000_0000_0001).hash(ctx)), nan);
+    assert_eq!(fed(|ctx| f32::from_bits(0xffc0_0001).hash(ctx)), fed(|ctx| f32::NAN.hash(ctx)));
+    assert_ne!(fed(|ctx| f64::INFINITY.hash(ctx)), fed(|ctx| f64::NEG_INFINITY.hash(ctx)));
+}
+
+#[test]
+fn slices_and_strings_are_length_prefixed() {
+    let mut abc = 3u64.to_le_bytes().to_vec();
+    abc.extend_from_slice(b"abc");
+    assert_eq!(fed(|ctx| "abc".hash(ctx)), abc);
+    assert_eq!(fed(|ctx| String::from("abc").hash(ctx)), abc);
+    assert_eq!(fed(|ctx| b"abc"[..].hash(ctx)), abc);
+
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    assert_ne!(["ab", "c"][..].digest(&f), ["a", "bc"][..].digest(&f));
+    assert_ne!(vec![vec![1u32, 2], vec![3]].digest(&f), vec![vec![1u32], vec![2, 3]].digest(&f));
+    // an empty slice and a slice holding one empty string
+    let empty: [&str; 0] = [];
+    assert_ne!(empty[..].digest(&f), [""][..].digest(&f));
+    assert_ne!([""][..].digest(&f), ["", ""][..].digest(&f));
+}
+
+#[test]
+fn tuples_options_and_arrays_are_not_length_prefixed() {
+    assert_eq!(fed(|ctx| (1u8, 2u8).hash(ctx)), [1, 2]);
+    assert_eq!(fed(|ctx| [1u8, 2u8].hash(ctx)), [1, 2]);
+    assert_eq!(fed(|ctx| (1u8, (2u16, [3u8; 2]), 4u8).hash(ctx)), [1, 2, 0, 3, 3, 4]);
+    assert_eq!(fed(|ctx| (1u8, 2u8, 3u8, 4u8, 5u8, 6u8, 7u8, 8u8, 9u8, 10u8, 11u8, 12u8).hash(ctx)),
+               [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+
+    assert_eq!(fed(|ctx| None::<(u8, u8)>.hash(ctx)), [0]);
+    assert_eq!(fed(|ctx| Some((1u8, 2u8)).hash(ctx)), [1, 1, 2]);
+    assert_eq!(fed(|ctx| Some(Some(0u8)).hash(ctx)), [1, 1, 0]);
+
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    assert_ne!(None::<u8>.digest(&f), Some(0u8).digest(&f));
+    assert_eq!((1u8, 2u8).digest(&f), f.digest(&[1, 2]));
+    assert_eq!([1u8, 2u8].digest(&f), f.digest(&[1, 2]));
+    assert_eq!([1u32, 2u32].digest(&f), (1u32, 2u32).digest(&f));
+}
+
+#[test]
+fn composite_keys_are_prefix_free() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    assert_ne!(("ab", "c").digest(&f), ("a", "bc").digest(&f));
+    assert_ne!((1u32, "ab").digest(&f), (1u32, "a").digest(&f));
+
+    assert_eq!(fed(|ctx| Ok::<u8, u16>(7).hash(ctx)), [0, 7]);
+    assert_eq!(fed(|ctx| Err::<u8, u16>(7).hash(ctx)), [1, 7, 0]);
+    assert_ne!(Ok::<u8, u8>(7).digest(&f), Err::<u8, u8>(7).digest(&f));
+    assert_ne!(Ok::<u8, u8>(0).digest(&f), None::<u8>.digest(&f));
+
+    // references hash as what they point to
+    fn encoding<T: Hash>(value: T) -> Vec<u8> {
+        fed(|ctx| value.hash(ctx))
+    }
+    let mut value = (1u32, "ab");
+    let by_ref: &(u32, &str) = &value;
+    assert_eq!(encoding(by_ref), encoding((1u32, "ab")));
+    let by_mut: &mut (u32, &str) = &mut value;
+    assert_eq!(encoding(by_mut), encoding((1u32, "ab")));
+    let nested: &&u64 = &&7;
+    assert_eq!(encoding(nested), encoding(7u64));
+}
+
+#[test]
+fn der_lengths_feed_short_and_long_forms() {
+    assert_eq!(fed(|ctx| ctx.update_der_length(0)), [0x00]);
+    assert_eq!(fed(|ctx| ctx.update_der_length(5)), [0x05]);
+    assert_eq!(fed(|ctx| ctx.update_der_length(127)), [0x7f]);
+    assert_eq!(fed(|ctx| ctx.update_der_length(128)), [0x81, 0x80]);
+    assert_eq!(fed(|ctx| ctx.update_der_length(200)), [0x81, 0xc8]);
+    assert_eq!(fed(|ctx| ctx.update_der_length(256)), [0x82, 0x01, 0x00]);
+    assert_eq!(fed(|ctx| ctx.update_der_length(0x1234_5678)),
+               [0x84, 0x12, 0x34, 0x56, 0x78]);
+}
+
+/// A hash function where every input collides.
+struct Constant;
+
+impl HashFunction for Constant {
+    type Context = Bytes;
+
+    fn init(&self) -> Bytes {
+        Bytes(Vec::new())
+    }
+
+    fn digest(&self, _bytes: &[u8]) -> Vec<u8> {
+        Vec::new()
+    }
+}
+
+#[test]
+fn content_ids_pair_the_length_and_digest() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    assert_eq!(f.content_id(b"hello"), (5, f.digest(b"hello")));
+    assert_eq!(f.content_id(b""), (0, f.digest(b"")));
+
+    // colliding digests are told apart by the length
+    assert_eq!(Constant.digest(b"a"), Constant.digest(b"ab"));
+    assert_ne!(Constant.content_id(b"a"), Constant.content_id(b"ab"));
+}
+
+/// No padding: 4 + 2 + 1 + 1 bytes, with u32 alignment.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Record {
+    id: u32,
+    kind: u16,
+    flags: u8,
+    level: u8,
+}
+
+#[test]
+fn pod_digests_hash_the_memory_representation() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let record = Record { id: 0x01020304, kind: 0x0506, flags: 7, level: 8 };
+
+    let mut fields = Vec::new();
+    fields.extend_from_slice(&record.id.to_ne_bytes());
+    fields.extend_from_slice(&record.kind.to_ne_bytes());
+    fields.push(record.flags);
+    fields.push(record.level);
+
+    assert_eq!(unsafe { digest_pod(&record, &f) }, f.digest(&fields));
+    assert_eq!(unsafe { digest_pod(&0x1234u64, &f) }, f.digest(&0x1234u64.to_ne_bytes()));
+}
+
+#[test]
+fn smart_pointers_are_a_level_of_nesting() {
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    use hash::traits::{DepthLimitExceeded, DepthLimited};
+
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let value = Box::new(Rc::new(Arc::new(7u32)));
+    assert_eq!(value.digest(&f), f.digest(&7u32.to_le_bytes()));
+    assert_eq!(f.digest_bounded(&value, 3), Ok(value.digest(&f)));
+    assert_eq!(f.digest_bounded(&value, 2), Err(DepthLimitExceeded));
+    assert_eq!(f.digest_bounded(&7u32, 0), Ok(7u32.digest(&f)));
+
+    let mut ctx = DepthLimited::new(f.init(), 1);
+    (Box::new(1u8), Box::new(2u8)).hash(&mut ctx);
+    assert_eq!(ctx.finish(), Ok((1u8, 2u8).digest(&f)));
+}
+
+#[test]
+fn versioned_digests_change_with_the_schema() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let payload = b"{\"id\":1}";
+    assert_eq!(f.digest_versioned(3, payload), f.digest_versioned(3, payload));
+    assert_ne!(f.digest_versioned(3, payload), f.digest_versioned(4, payload));
+    assert_ne!(f.digest_versioned(3, payload), f.digest(payload));
+
+    let mut framed = 3u32.to_le_bytes().to_vec();
+    framed.extend_from_slice(payload);
+    assert_eq!(f.digest_versioned(3, payload), f.digest(&framed));
+}
+
+#[test]
+fn variants_separate_tags() {
+    let mut expected = 7u64.to_le_bytes().to_vec();
+    expected.extend_from_slice(&3u64.to_le_bytes());
+    expected.extend_from_slice(b"abc");
+    assert_eq!(fed(|ctx| ctx.update_variant(7, b"abc")), expected);
+
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let digest = |tag, payload: &[u8]| {
+        let mut ctx = f.init();
+        ctx.update_variant(tag, payload);
+        ctx.finish()
+    };
+    assert_ne!(digest(1, b"payload"), digest(2, b"payload"));
+    assert_ne!(digest(1, b""), digest(2, b""));
+    assert_eq!(digest(1, b"payload"), digest(1, b"payload"));
+
+    // the framing keeps the payload from blending into what follows
+    let framed = |split: usize| fed(|ctx| {
+        ctx.update_variant(1, &b"abcd"[..split]);
+        ctx.update(&b"abcd"[split..]);
+    });
+    assert_ne!(framed(1), framed(2));
+}
+
+/// Check that a reused context gives the same digests as fresh ones.
+fn check_pool<F: HashFunction>(f: F)
+    where F::Context: ResettableContext,
+          <F::Context as HashContext>::Result: PartialEq + std::fmt::Debug
+{
+    let inputs: [&[u8]; 5] = [b"", b"a", b"", &[7; 100], b"abc"];
+    let mut ctx = f.init();
+    for input in &inputs {
+        ctx.update(input);
+        assert_eq!(ctx.finish_reset(), f.digest(input), "{:?}", input);
+    }
+    let mut pool = f.hasher_pool();
+    for input in &inputs {
+        assert_eq!(pool.digest(input), f.digest(input), "{:?}", input);
+    }
+    assert_eq!(pool.digest_value(&(1u32, "abc")), (1u32, "abc").digest(&f));
+}
+
+#[test]
+fn reset_contexts_match_fresh_ones() {
+    check_pool(SipHashFunction::new_with_keys(K0, K1));
+    check_pool(SipHash128Function::new_with_keys(K0, K1));
+    check_pool(FnvHashFunction::new());
+    check_pool(FxHashFunction::new());
+    check_pool(Xxh64HashFunction::new());
+    check_pool(Xxh3HashFunction::new());
+    check_pool(Crc32HashFunction::new());
+    check_pool(Crc32cHashFunction::new());
+    check_pool(Sha256HashFunction::new());
+    check_pool(Murmur3x32HashFunction::new());
+    check_pool(Murmur3x128HashFunction::new());
+    check_pool(BuzHash::new(16));
+    // a partial leaf, then whole leaves, must not leak into the next digest
+    check_pool(TreeHash::new(SipHashFunction::new_with_keys(K0, K1), 16));
+}
@@ -0,0 +1,58 @@
+use hash::sip::SipHashFunction;
+use hash::traits::{Hash, HashContext, HashFunction};
+use hash::tree::TreeHash;
+
+const K0: u64 = 0x0706050403020100;
+const K1: u64 = 0x0f0e0d0c0b0a0908;
+
+fn pattern(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 31 % 251) as u8).collect()
+}
+
+#[test]
+fn tree_digest_combines_the_leaf_digests() {
+    let f = SipHashFunction::new_with_keys(K0, K1);
+    let tree = TreeHash::new(f, 16);
+    let data = pattern(40);
+
+    let mut ctx = f.init();
+    ctx.write_u64(40);
+    ctx.write_u64(16);
+    for leaf in data.chunks(16) {
+        f.digest(leaf).hash(&mut ctx);
+    }
+    assert_eq!(tree.digest(&data), ctx.finish());
+
+    // the length and leaf size are part of the digest
+    assert_ne!(tree.digest(b""), f.digest(b""));
+    assert_ne!(tree.digest(&data), TreeHash::new(f, 8).digest(&data));
+}
+
+#[test]
+fn streamed_tree_digest_matches_one_shot() {
+    let tree = TreeHash::new(SipHashFunction::new_with_keys(K0, K1), 64);
+    let data = pattern(1000);
+    for &len in &[0, 1, 63, 64, 65, 128, 1000] {
+        for &chunk in &[1, 7, 64, 100, 1000] {
+            let mut ctx = tree.init();
+            for piece in data[..len].chunks(chunk) {
+                ctx.update(piece);
+            }
+            assert_eq!(ctx.finish(), tree.digest(&data[..len]), "length {}, chunks of {}", len, chunk);
+        }
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_tree_digest_matches_sequential() {
+    let tree = TreeHash::new(SipHashFunction::new_with_keys(K0, K1), 1024);
+    let data = pattern(100_000);
+    for &len in &[0, 1, 1024, 1025, 100_000] {
+        assert_eq!(tree.digest_parallel(&data[..len]), tree.digest(&data[..len]), "length {}", len);
+    }
+    for threads in 1..4 {
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+        assert_eq!(pool.install(|| tree.digest_parallel(&data)), tree.digest(&data), "{} threads", threads);
+    }
+}
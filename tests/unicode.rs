@@ -0,0 +1,18 @@
+use hash::sip::SipHashFunction;
+use hash::traits::HashFunction;
+
+#[test]
+fn nfc_digests_ignore_the_normalization_form() {
+    let f = SipHashFunction::new_with_keys(1, 2);
+    let composed = "caf\u{e9}";
+    let decomposed = "cafe\u{301}";
+    assert_ne!(f.digest(composed.as_bytes()), f.digest(decomposed.as_bytes()));
+    assert_eq!(f.digest_nfc(composed), f.digest_nfc(decomposed));
+    assert_eq!(f.digest_nfc(composed), f.digest(composed.as_bytes()));
+    assert_ne!(f.digest_nfc("cafe"), f.digest_nfc(composed));
+
+    // longer than the internal buffer
+    let long_composed = composed.repeat(50);
+    let long_decomposed = decomposed.repeat(50);
+    assert_eq!(f.digest_nfc(&long_decomposed), f.digest(long_composed.as_bytes()));
+}
@@ -0,0 +1,27 @@
+use std::collections::HashSet;
+
+use hash::sip::SipHashFunction;
+use hash::traits::HashFunction;
+use hash::words::{EVEN, ODD};
+
+#[test]
+fn word_lists_are_distinct() {
+    let words: HashSet<&str> = EVEN.iter().chain(ODD.iter()).copied().collect();
+    assert_eq!(words.len(), 512);
+}
+
+#[test]
+fn digests_spell_their_bytes() {
+    let f = SipHashFunction::new_with_keys(1, 2);
+    let digest = f.digest(b"fingerprint").to_be_bytes();
+    let words = f.digest_words(b"fingerprint", 4);
+    assert_eq!(words, [EVEN[digest[0] as usize], ODD[digest[1] as usize],
+                       EVEN[digest[2] as usize], ODD[digest[3] as usize]]);
+    assert_eq!(f.digest_words(b"fingerprint", 4), words);
+    assert_eq!(f.digest_words(b"fingerprint", 20).len(), 8);
+
+    let phrases: HashSet<Vec<&str>> = (0..1000u32)
+        .map(|i| f.digest_words(&i.to_le_bytes(), 4))
+        .collect();
+    assert_eq!(phrases.len(), 1000);
+}
@@ -0,0 +1,115 @@
+use hash::traits::{HashContext, HashFunction};
+use hash::xxh::{Xxh3HashFunction, Xxh64HashFunction};
+
+const SEED: u64 = 0x9e3779b97f4a7c15;
+
+/// XXH64 with seed 0, from the reference implementation.
+const XXH64_STRINGS: [(&[u8], u64); 4] = [
+    (b"", 0xef46db3751d8e999),
+    (b"a", 0xd24ec4f1a98c6e5b),
+    (b"abc", 0x44bc2cf5ad770999),
+    (b"The quick brown fox jumps over the lazy dog", 0x0b242d361fda71bc),
+];
+
+/// XXH64 of the first `len` bytes of `pattern()`, with seed 0 and `SEED`.
+const XXH64_LENGTHS: [(usize, u64, u64); 16] = [
+    (0, 0xef46db3751d8e999, 0xc4349fc93c010000),
+    (1, 0xe934a84adb052768, 0x126bb57a12364aa5),
+    (3, 0xe5d2be4ae4b3469a, 0x14551df805bf04f2),
+    (4, 0x3b4d7f7c6bd1ae90, 0x6f79bc2062402943),
+    (7, 0xf952f1901a5afc9b, 0xd5d902e89b5ca4c3),
+    (8, 0x506834122cb7b4d0, 0x55fd7982d8c9ec98),
+    (9, 0xba2f457c2914d838, 0x9173756419fd08bf),
+    (15, 0x235fae1dcfeffcdd, 0x424b6f33a9463009),
+    (16, 0x6d4c29b301168385, 0xdf17cdfa4d3e6b35),
+    (17, 0x0240748a02fa9849, 0x01ba65c7cc454fa1),
+    (128, 0x148784ce52b352b6, 0xd17a3a7e9eeba1f7),
+    (129, 0xc0ce4b738e488307, 0x19c38b0a4ee35d0c),
+    (240, 0x1e01bc7b208f41dd, 0xe5fee8535cf0c443),
+    (241, 0x312db39e3c3e84f7, 0x78eb42f40711ae0a),
+    (1024, 0x664c62c6afdbeca3, 0x2eeda1970f91155a),
+    (5000, 0xe3851ef69b545e15, 0xa3605afeeae452d3),
+];
+
+/// Same as `XXH64_STRINGS`, for XXH3-64.
+const XXH3_STRINGS: [(&[u8], u64); 4] = [
+    (b"", 0x2d06800538d394c2),
+    (b"a", 0xe6c632b61e964e1f),
+    (b"abc", 0x78af5f94892f3950),
+    (b"The quick brown fox jumps over the lazy dog", 0xce7d19a5418fb365),
+];
+
+/// Same as `XXH64_LENGTHS`, for XXH3-64.
+const XXH3_LENGTHS: [(usize, u64, u64); 16] = [
+    (0, 0x2d06800538d394c2, 0x602b0e2cd6662c8b),
+    (1, 0xc44bdff4074eecdb, 0x062b185e4e01441a),
+    (3, 0x3698b80191e625f9, 0xaa72591d27a41b51),
+    (4, 0x2e4ac2f1c52157fc, 0xcea59079968ca52a),
+    (7, 0xd02b16c5070846f4, 0xf2c7f9a744c1ede8),
+    (8, 0x60e1baa91347a1f2, 0x0a11bcc58fe921b7),
+    (9, 0x9c88fc32c37b56cb, 0x0989cc9609a4bb8b),
+    (15, 0x3a0ea85d10d9921a, 0x6f9430ca2e9f59bd),
+    (16, 0x8626370b70d8ac70, 0x00df11e82add916a),
+    (17, 0xaa7a5f479409a664, 0xfb15fd72f59ad695),
+    (128, 0x040e97756280237a, 0xdbcefef443e14cd8),
+    (129, 0xf0f4dc98ce461dfd, 0xf0ee328305b1419d),
+    (240, 0x8acecb90be72b9c3, 0xd17ee30697f7ccce),
+    (241, 0x82d3ebf50a3e9c42, 0x6bbb4dfa66db5ed8),
+    (1024, 0xcbdff56b050f2c1c, 0x6b6204a87d6e5534),
+    (5000, 0xa092d73116291201, 0xb22eda31ecdef1ec),
+];
+
+/// The bytes the length vectors are computed on.
+fn pattern(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 31 % 251) as u8).collect()
+}
+
+/// Feed `data` to `ctx` in `chunk`-byte updates.
+fn update_chunks<C: HashContext>(ctx: &mut C, data: &[u8], chunk: usize) {
+    for piece in data.chunks(chunk) {
+        ctx.update(piece);
+    }
+}
+
+#[test]
+fn xxh64_reference_vectors() {
+    let f = Xxh64HashFunction::new();
+    for &(msg, expected) in &XXH64_STRINGS {
+        assert_eq!(f.digest(msg), expected, "{:?}", msg);
+    }
+    let data = pattern(5000);
+    let seeded = Xxh64HashFunction::new_with_seed(SEED);
+    for &(len, expected, expected_seeded) in &XXH64_LENGTHS {
+        assert_eq!(f.digest(&data[..len]), expected, "length {}", len);
+        assert_eq!(seeded.digest(&data[..len]), expected_seeded, "seeded, length {}", len);
+        for &chunk in &[1, 7, 32, 100] {
+            let mut ctx = f.init();
+            update_chunks(&mut ctx, &data[..len], chunk);
+            assert_eq!(ctx.finish(), expected, "length {}, chunks of {}", len, chunk);
+        }
+    }
+}
+
+#[test]
+fn xxh3_reference_vectors() {
+    let f = Xxh3HashFunction::new();
+    for &(msg, expected) in &XXH3_STRINGS {
+        assert_eq!(f.digest(msg), expected, "{:?}", msg);
+    }
+    let data = pattern(5000);
+    let seeded = Xxh3HashFunction::new_with_seed(SEED);
+    for &(len, expected, expected_seeded) in &XXH3_LENGTHS {
+        assert_eq!(f.digest(&data[..len]), expected, "length {}", len);
+        assert_eq!(seeded.digest(&data[..len]), expected_seeded, "seeded, length {}", len);
+        // around the 240-byte short input limit and the 1024-byte block
+        for &chunk in &[1, 7, 64, 239, 1000] {
+            let mut ctx = f.init();
+            update_chunks(&mut ctx, &data[..len], chunk);
+            assert_eq!(ctx.finish(), expected, "length {}, chunks of {}", len, chunk);
+
+            let mut ctx = seeded.init();
+            update_chunks(&mut ctx, &data[..len], chunk);
+            assert_eq!(ctx.finish(), expected_seeded, "seeded, length {}, chunks of {}", len, chunk);
+        }
+    }
+}